@@ -1,4 +1,4 @@
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand, ValueEnum};
 
 extern crate clap_show;
 
@@ -25,16 +25,22 @@ struct Positional {
 /// This explains how the application works on details. Probably a good to
 /// have an introduction to the commands and the purpose of it.
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, author, about, long_about = None)]
 #[command(propagate_version = true)]
+#[command(after_help = "Run `clap-show help <COMMAND>` for the details of a command.")]
 struct Cli {
     #[command(subcommand)]
-    command: Commands
+    command: Commands,
+
+    /// Print more details
+    #[arg(short, long, global = true)]
+    verbose: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Example of how arguments are treated on the application documentation
+    #[command(visible_alias = "args")]
     Arguments(ArgumentArgs),
 
     /// Positional short help
@@ -50,6 +56,7 @@ enum Commands {
     /// flag short help
     ///
     /// flag arguments long help
+    #[command(short_flag = 'F')]
     Flags(FlagArgs),
 
     /// markdown short help
@@ -67,24 +74,57 @@ struct ArgumentArgs {
 
 
 #[derive(Args)]
+#[command(group(ArgGroup::new("input").args(["file", "stdin"]).required(true)))]
 struct FlagArgs {
     /// Flag short help
     ///
     /// Flag long help
     #[arg(short='f', long="flag")]
     flag: String,
+
+    /// When to use colors
+    #[arg(long, visible_alias = "colour", value_enum, default_value_t = Color::Auto, env = "CLAP_SHOW_COLOR")]
+    color: Color,
+
+    /// Read the input from a file
+    #[arg(long, help_heading = "Input")]
+    file: Option<String>,
+
+    /// Read the input from the standard input
+    #[arg(long, help_heading = "Input")]
+    stdin: bool,
+
+    /// Print the output as JSON
+    #[arg(long, conflicts_with = "color", requires = "file")]
+    json: bool,
+
+    /// Tags to attach to the output
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    tags: Vec<String>,
+
+    /// Pause between retries
+    #[arg(long, value_name = "SECONDS", require_equals = true, num_args = 0..=1, default_missing_value = "1")]
+    retry: Option<u32>,
+}
+
+#[derive(Clone, ValueEnum)]
+enum Color {
+    /// Use colors when writing to a terminal
+    Auto,
+    /// Always use colors
+    Always,
+    /// Never use colors
+    Never,
 }
 
 
 #[derive(Args)]
 struct MarkdownArgs {}
 
-fn main() {
-    let cli = Cli::parse();
+fn main() -> clap_show::Result<()> {
+    let _cli = Cli::parse();
     let command = Cli::command();
 
-    match cli.command {
-       _ => clap_show::help_command(&command)
-    };
+    clap_show::help_command(&command)
 }
 
@@ -0,0 +1,469 @@
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
+
+use clap::Command;
+
+use crate::extract::{extract_with, Settings};
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use crate::Theme;
+use crate::{json, man, markdown, site, DocPage, Engine, Error, ManPage, Renderer, Result, SitePage, Verbosity};
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Clone, Debug)]
+enum Source {
+    Text(String),
+    File(PathBuf),
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+impl Source {
+    fn load(&self) -> io::Result<String> {
+        match self {
+            Source::Text(text) => Ok(text.clone()),
+            Source::File(path) => fs::read_to_string(path).map_err(|err| {
+                io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+            }),
+        }
+    }
+}
+
+/// Configure how the documentation for a command is generated.
+///
+/// HTML is rendered with the default [`Engine`], which can be changed with
+/// [`ClapShow::engine`], or with a custom [`Renderer`]. The bundled templates
+/// and partials can be replaced by name:
+///
+/// - `template`: the single HTML page, with the menu beside the content, or
+///   folded behind a button on small screens.
+/// - `usage-partial`: usage, commands, arguments and options of a command.
+///   The lists use the `heading_level` of the command, e.g. `2` for `<h2>`
+///   headings on the site pages.
+/// - `arg-details`: default values, possible values, environment variable and
+///   relationships of an argument.
+/// - `style`: the stylesheet shared by every HTML page, see
+///   [`ClapShow::stylesheet`].
+/// - `search`: the search box of every HTML page, which searches the
+///   commands and arguments without any network access.
+/// - `nav`: the collapsible tree of the subcommands, in the menu of the
+///   single page and in the index of the static site.
+/// - `site-page`: a command page of the static site.
+/// - `site-index`: the index page of the static site.
+///
+/// Any other name registers a new partial that custom templates can use.
+/// Templates and partials without an override fall back to the bundled ones.
+///
+/// # Example
+///
+/// ```
+/// use clap::Command;
+/// use clap_show::ClapShow;
+///
+/// let command = Command::new("mycli").about("Does things");
+/// # #[cfg(any(feature = "handlebars", feature = "ramhorns"))] {
+/// let html = ClapShow::new(&command)
+///     .template("<h1>{{main.cmd_chain}}</h1>{{> footer}}")
+///     .partial("footer", "<footer>ACME</footer>")
+///     .render()?;
+///
+/// assert_eq!(html, "<h1>mycli</h1><footer>ACME</footer>");
+/// # }
+/// # Ok::<(), clap_show::Error>(())
+/// ```
+pub struct ClapShow<'a> {
+    command: &'a Command,
+    engine: Option<Engine>,
+    renderer: Option<Box<dyn Renderer + 'a>>,
+    settings: Settings,
+    // Only the bundled template engines use the settings below
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    templates: BTreeMap<String, Source>,
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    trusted_html: bool,
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    theme: Theme,
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    css: Vec<String>,
+}
+
+impl fmt::Debug for ClapShow<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ClapShow");
+        debug
+            .field("command", &self.command.get_name())
+            .field("engine", &self.engine)
+            .field("renderer", &self.renderer.as_ref().map(|_| ".."))
+            .field("settings", &self.settings);
+        #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+        debug
+            .field("templates", &self.templates)
+            .field("trusted_html", &self.trusted_html)
+            .field("theme", &self.theme)
+            .field("css", &self.css);
+        debug.finish()
+    }
+}
+
+impl<'a> ClapShow<'a> {
+    /// Generate the documentation for `command` with the bundled templates.
+    pub fn new(command: &'a Command) -> Self {
+        ClapShow {
+            command,
+            engine: Engine::default_engine(),
+            renderer: None,
+            settings: Settings::default(),
+            #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+            templates: BTreeMap::new(),
+            #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+            trusted_html: false,
+            #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+            theme: Theme::default(),
+            #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+            css: Vec::new(),
+        }
+    }
+
+    /// Render the HTML pages with `engine`.
+    ///
+    /// Custom templates and partials must use the syntax of that engine.
+    pub fn engine(mut self, engine: Engine) -> Self {
+        self.engine = Some(engine);
+        self
+    }
+
+    /// Render the HTML pages with a custom [`Renderer`].
+    ///
+    /// The renderer is in charge of its own templates, so the ones registered
+    /// with [`ClapShow::template`] and [`ClapShow::partial`] are ignored.
+    pub fn renderer<R: Renderer + 'a>(mut self, renderer: R) -> Self {
+        self.renderer = Some(Box::new(renderer));
+        self
+    }
+
+    /// Prefer the short or the long help texts, see [`Verbosity`].
+    ///
+    /// Applies to every output. Defaults to [`Verbosity::Full`].
+    ///
+    /// ```
+    /// use clap::Command;
+    /// use clap_show::{ClapShow, Verbosity};
+    ///
+    /// let command = Command::new("mycli")
+    ///     .about("Does things")
+    ///     .long_about("Does things.\n\nAll of them, in great details.");
+    /// let markdown = ClapShow::new(&command)
+    ///     .verbosity(Verbosity::Summary)
+    ///     .render_markdown()?;
+    ///
+    /// assert!(markdown.contains("Does things"));
+    /// assert!(!markdown.contains("great details"));
+    /// # Ok::<(), clap_show::Error>(())
+    /// ```
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.settings.verbosity = verbosity;
+        self
+    }
+
+    /// Include the `--help` and `--version` flags and the `help` subcommand
+    /// that clap generates. Defaults to `true`.
+    ///
+    /// ```
+    /// use clap::Command;
+    /// use clap_show::ClapShow;
+    ///
+    /// let command = Command::new("mycli").subcommand(Command::new("deploy"));
+    /// let markdown = ClapShow::new(&command).help_flags(false).render_markdown()?;
+    ///
+    /// assert!(!markdown.contains("--help"));
+    /// assert!(!markdown.contains("mycli help"));
+    /// # Ok::<(), clap_show::Error>(())
+    /// ```
+    pub fn help_flags(mut self, include: bool) -> Self {
+        self.settings.help_flags = include;
+        self
+    }
+
+    /// Include the aliases of the commands and arguments that are hidden from
+    /// `--help`, along with the visible ones. Defaults to `false`.
+    ///
+    /// ```
+    /// use clap::{Arg, Command};
+    /// use clap_show::ClapShow;
+    ///
+    /// let command = Command::new("mycli")
+    ///     .arg(Arg::new("color").long("color").visible_alias("colour").alias("colr"));
+    ///
+    /// let markdown = ClapShow::new(&command).render_markdown()?;
+    /// assert!(markdown.contains("--colour"));
+    /// assert!(!markdown.contains("--colr"));
+    ///
+    /// let markdown = ClapShow::new(&command).hidden_aliases(true).render_markdown()?;
+    /// assert!(markdown.contains("--colr"));
+    /// # Ok::<(), clap_show::Error>(())
+    /// ```
+    pub fn hidden_aliases(mut self, include: bool) -> Self {
+        self.settings.hidden_aliases = include;
+        self
+    }
+
+    /// Write the help texts into the HTML pages as they are, instead of
+    /// escaping them. Defaults to `false`.
+    ///
+    /// By default, a help text like `Parse a Vec<T>` is displayed as written.
+    /// Only enable this when every help text of the command is trusted HTML,
+    /// since it can then inject any markup, scripts included. Custom
+    /// [`Renderer`]s handle escaping themselves.
+    ///
+    /// ```
+    /// use clap::{Arg, Command};
+    /// use clap_show::ClapShow;
+    ///
+    /// let command = Command::new("mycli").arg(Arg::new("fast").long("fast").help("Go <b>fast</b>"));
+    /// # #[cfg(any(feature = "handlebars", feature = "ramhorns"))] {
+    ///
+    /// let html = ClapShow::new(&command).render()?;
+    /// assert!(html.contains("Go &lt;b&gt;fast&lt;/b&gt;"));
+    ///
+    /// let html = ClapShow::new(&command).trusted_html(true).render()?;
+    /// assert!(html.contains("Go <b>fast</b>"));
+    /// # }
+    /// # Ok::<(), clap_show::Error>(())
+    /// ```
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn trusted_html(mut self, trusted: bool) -> Self {
+        self.trusted_html = trusted;
+        self
+    }
+
+    /// Style the HTML pages with one of the bundled themes. Defaults to
+    /// [`Theme::Default`].
+    ///
+    /// Ignored when the whole stylesheet is replaced with [`ClapShow::stylesheet`].
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Append `source` to the stylesheet of the HTML pages.
+    ///
+    /// The CSS comes after the theme, so it can override its custom properties
+    /// as well as any rule. Calling it again appends more CSS.
+    ///
+    /// ```
+    /// use clap::Command;
+    /// use clap_show::{ClapShow, Theme};
+    ///
+    /// let command = Command::new("mycli");
+    /// # #[cfg(any(feature = "handlebars", feature = "ramhorns"))] {
+    /// let html = ClapShow::new(&command)
+    ///     .theme(Theme::Dark)
+    ///     .css(":root { --accent-color: orange; }")
+    ///     .render()?;
+    ///
+    /// assert!(html.contains("color-scheme: dark"));
+    /// assert!(html.contains("--accent-color: orange"));
+    /// # }
+    /// # Ok::<(), clap_show::Error>(())
+    /// ```
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn css<S: Into<String>>(mut self, source: S) -> Self {
+        self.css.push(source.into());
+        self
+    }
+
+    /// Replace the whole stylesheet of the HTML pages, theme included, with
+    /// `source`. CSS added with [`ClapShow::css`] is still appended to it.
+    ///
+    /// The bundled stylesheet relies on the custom properties of the themes,
+    /// see [`Theme`].
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn stylesheet<S: Into<String>>(self, source: S) -> Self {
+        self.partial("style", source)
+    }
+
+    /// Replace the single page template with `source`.
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn template<S: Into<String>>(self, source: S) -> Self {
+        self.partial("template", source)
+    }
+
+    /// Replace the single page template with the contents of the file at `path`.
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn template_file<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.partial_file("template", path)
+    }
+
+    /// Register `source` as the partial or template called `name`.
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn partial<S: Into<String>>(mut self, name: &str, source: S) -> Self {
+        self.templates
+            .insert(name.to_string(), Source::Text(source.into()));
+        self
+    }
+
+    /// Register the contents of the file at `path` as the partial or template
+    /// called `name`.
+    ///
+    /// The file is read when the documentation is rendered.
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    pub fn partial_file<P: Into<PathBuf>>(mut self, name: &str, path: P) -> Self {
+        self.templates
+            .insert(name.to_string(), Source::File(path.into()));
+        self
+    }
+
+    /// Render the documentation as a single HTML page.
+    pub fn render(&self) -> Result<String> {
+        self.with_renderer(|renderer| renderer.render_page(&self.extract()))
+    }
+
+    /// Write the documentation as a single HTML page into an [`io::Write`] sink.
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(self.render()?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Render the documentation as a Markdown document.
+    pub fn render_markdown(&self) -> Result<String> {
+        let mut out = String::new();
+        markdown::render(&self.extract(), &mut out)?;
+        Ok(out)
+    }
+
+    /// Render the command tree as JSON, following [`JSON_SCHEMA`](crate::JSON_SCHEMA).
+    pub fn render_json(&self) -> Result<String> {
+        Ok(json::render(&self.extract())?)
+    }
+
+    /// Render one man page for the command and one for each of its subcommands.
+    pub fn render_man_pages(&self) -> Result<Vec<ManPage>> {
+        Ok(man::render(&self.extract())?)
+    }
+
+    /// Write the man pages into the `dir` directory.
+    ///
+    /// The directory is created when missing. Returns the paths of the written pages.
+    pub fn write_man_pages<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>> {
+        let page = self.extract();
+        let pages = man::render(&page)?;
+        // There is one page per command, in the same order
+        let commands = std::iter::once(&page.main).chain(&page.subcommands);
+        let files = pages
+            .into_iter()
+            .zip(commands)
+            .map(|(p, t)| (p.name, p.content, t.cmd_chain.clone()));
+        write_files(dir.as_ref(), &page.main.cmd_chain, files)
+    }
+
+    /// Render a static site, with one HTML page per command and an index.
+    pub fn render_site(&self) -> Result<Vec<SitePage>> {
+        self.with_renderer(|renderer| renderer.render_site(&self.extract()))
+    }
+
+    /// Write the static site into the `dir` directory.
+    ///
+    /// The directory is created when missing. Returns the paths of the written pages.
+    pub fn write_site<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>> {
+        let page = self.extract();
+        let pages = self.with_renderer(|renderer| renderer.render_site(&page))?;
+        // The index, the search index and the pages of custom renderers are
+        // put down to the main command
+        let commands = site::commands(&page);
+        let files = pages.into_iter().map(|p| {
+            let command = commands.get(&p.name).unwrap_or(&page.main.cmd_chain).clone();
+            (p.name, p.content, command)
+        });
+        write_files(dir.as_ref(), &page.main.cmd_chain, files)
+    }
+
+    fn extract(&self) -> DocPage {
+        extract_with(self.command, &self.settings)
+    }
+
+    /// Call `f` with the custom renderer, or with the selected engine loaded
+    /// with the bundled templates and the overrides.
+    fn with_renderer<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&dyn Renderer) -> Result<T>,
+    {
+        if let Some(renderer) = &self.renderer {
+            return f(renderer.as_ref());
+        }
+
+        match self.engine.ok_or(Error::NoRenderer)? {
+            #[cfg(feature = "handlebars")]
+            Engine::Handlebars => {
+                let mut renderer = crate::HandlebarsRenderer::new()?;
+                renderer.trusted_html(self.trusted_html);
+                self.register(|name, source| renderer.register(name, source))?;
+                f(&renderer)
+            }
+            #[cfg(feature = "ramhorns")]
+            Engine::Ramhorns => {
+                let mut renderer = crate::RamhornsRenderer::new()?;
+                renderer.trusted_html(self.trusted_html);
+                self.register(|name, source| renderer.register(name, source))?;
+                f(&renderer)
+            }
+        }
+    }
+
+    /// Load every template override and hand it to `register`.
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    fn register<F>(&self, mut register: F) -> Result<()>
+    where
+        F: FnMut(&str, &str) -> Result<()>,
+    {
+        let load = |name: &str, source: &Source| {
+            source.load().map_err(|err| Error::Template {
+                name: name.to_string(),
+                source: Box::new(err),
+            })
+        };
+
+        for (name, source) in &self.templates {
+            if name != "style" {
+                register(name, &load(name, source)?)?;
+            }
+        }
+
+        // The stylesheet is made of the theme, or of its replacement, followed
+        // by the custom CSS
+        let mut style = match self.templates.get("style") {
+            Some(source) => load("style", source)?,
+            None => self.theme.stylesheet(),
+        };
+        for css in &self.css {
+            style.push('\n');
+            style.push_str(css);
+        }
+        register("style", &style)?;
+
+        Ok(())
+    }
+}
+
+/// Write every `(name, content, command)` file into `dir`, which documents
+/// the `command` command chain.
+fn write_files<I>(dir: &Path, command: &str, files: I) -> Result<Vec<PathBuf>>
+where
+    I: IntoIterator<Item = (String, String, String)>,
+{
+    let error = |path: &Path, command: &str, source| Error::Write {
+        command: command.to_string(),
+        path: path.to_path_buf(),
+        source,
+    };
+    fs::create_dir_all(dir).map_err(|err| error(dir, command, err))?;
+
+    let mut paths = Vec::new();
+    for (name, content, command) in files {
+        let path = dir.join(name);
+        fs::write(&path, content).map_err(|err| error(&path, &command, err))?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
@@ -0,0 +1,140 @@
+use std::error::Error as StdError;
+use std::path::PathBuf;
+use std::{fmt, io};
+
+/// Result type returned by every public entry point of `clap_show`.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors that can happen while generating the documentation.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A template or partial could not be registered with the template engine.
+    Template {
+        /// Name of the template that failed to load.
+        name: String,
+        /// Error reported by the template engine.
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    /// The template engine failed while rendering a command.
+    Render {
+        /// Command chain (e.g. `mycli deploy`) that was being rendered.
+        command: String,
+        /// Error reported by the template engine.
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    /// A template helper was called without one of its required parameters.
+    MissingParam {
+        /// Command chain (e.g. `mycli deploy`) that was being rendered.
+        command: String,
+        /// Name of the helper that was called.
+        helper: String,
+        /// Position of the missing parameter.
+        index: usize,
+    },
+    /// HTML was requested but no template engine is available.
+    ///
+    /// Enable the `handlebars` or `ramhorns` feature, or plug a custom
+    /// [`Renderer`](crate::Renderer).
+    NoRenderer,
+    /// The documentation model could not be serialized to JSON.
+    Json(serde_json::Error),
+    /// The rendered documentation could not be written to an [`io::Write`] sink.
+    Io(io::Error),
+    /// A page or its directory could not be written to the file system.
+    Write {
+        /// Command chain (e.g. `mycli deploy`) documented by the page.
+        command: String,
+        /// Path of the file or directory that could not be written.
+        path: PathBuf,
+        /// Error reported by the file system.
+        source: io::Error,
+    },
+    /// The rendered documentation could not be written to a [`fmt::Write`] sink.
+    Fmt(fmt::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Template { name, source } => {
+                write!(f, "unable to load template `{}`: {}", name, source)
+            }
+            Error::Render { command, source } => {
+                write!(f, "unable to render `{}`: {}", command, source)
+            }
+            Error::MissingParam {
+                command,
+                helper,
+                index,
+            } => write!(
+                f,
+                "unable to render `{}`: helper `{}` requires a parameter at index {}",
+                command, helper, index
+            ),
+            Error::NoRenderer => write!(f, "no template engine available to render HTML"),
+            Error::Json(err) => write!(f, "unable to serialize documentation: {}", err),
+            Error::Io(err) => write!(f, "unable to write documentation: {}", err),
+            Error::Write { command, path, source } => {
+                write!(f, "unable to write `{}` for `{}`: {}", path.display(), command, source)
+            }
+            Error::Fmt(err) => write!(f, "unable to write documentation: {}", err),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Template { source, .. } | Error::Render { source, .. } => Some(source.as_ref()),
+            Error::MissingParam { .. } | Error::NoRenderer => None,
+            Error::Json(err) => Some(err),
+            Error::Io(err) | Error::Write { source: err, .. } => Some(err),
+            Error::Fmt(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<fmt::Error> for Error {
+    fn from(err: fmt::Error) -> Self {
+        Error::Fmt(err)
+    }
+}
+
+#[cfg(feature = "handlebars")]
+impl Error {
+    pub(crate) fn template(name: &str, err: handlebars::TemplateError) -> Self {
+        Error::Template {
+            name: name.to_string(),
+            source: Box::new(err),
+        }
+    }
+
+    pub(crate) fn render(command: &str, err: handlebars::RenderError) -> Self {
+        match err.reason() {
+            handlebars::RenderErrorReason::ParamNotFoundForIndex(helper, index) => {
+                Error::MissingParam {
+                    command: command.to_string(),
+                    helper: helper.to_string(),
+                    index: *index,
+                }
+            }
+            _ => Error::Render {
+                command: command.to_string(),
+                source: Box::new(err),
+            },
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+
+use clap::builder::{StyledStr, ValueRange};
+use clap::{Arg, ArgAction, ArgGroup, Command};
+
+use crate::slug::{Slugger, TEMPLATE_IDS};
+use crate::usage;
+use crate::{
+    DocArg, DocCommand, DocGroup, DocInheritedArg, DocPage, DocPossibleValue, DocSection,
+    DocSubcommand, DocTreeNode,
+};
+
+/// Extract the documentation model of `command` and all of its subcommands.
+///
+/// This is the model every backend renders, so it can feed other tooling, such
+/// as search indexers or changelog generators, without walking the
+/// [`clap::Command`] again.
+///
+/// # Example
+///
+/// ```
+/// use clap::{Arg, Command};
+///
+/// let command = Command::new("mycli")
+///     .subcommand(Command::new("deploy").arg(Arg::new("force").long("force")));
+/// let page = clap_show::extract(&command);
+///
+/// assert_eq!(page.subcommands[0].cmd_chain, "mycli deploy");
+/// assert_eq!(page.subcommands[0].options[0].flags, "--force <force>");
+/// ```
+pub fn extract(command: &Command) -> DocPage {
+    extract_with(command, &Settings::default())
+}
+
+/// How much help text the documentation shows.
+///
+/// Commands and arguments can have a short help, set with `about` and `help`,
+/// and a long one, set with `long_about` and `long_help`. The same goes for
+/// the version and the texts before and after the help of a command. Either
+/// way, when the preferred text is missing the other one is used, so nothing
+/// documented goes missing. Subcommand listings always prefer the short help,
+/// like `--help`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Verbosity {
+    /// Prefer the short help, for compact reference pages.
+    Summary,
+    /// Prefer the long help.
+    #[default]
+    Full,
+}
+
+/// Options of the extraction, set through [`ClapShow`](crate::ClapShow).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Settings {
+    pub(crate) verbosity: Verbosity,
+    /// Whether to document the generated `--help` and `--version` flags and
+    /// `help` subcommand.
+    pub(crate) help_flags: bool,
+    /// Whether to document the aliases hidden from `--help` along with the
+    /// visible ones.
+    pub(crate) hidden_aliases: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            verbosity: Verbosity::default(),
+            help_flags: true,
+            hidden_aliases: false,
+        }
+    }
+}
+
+/// A global argument, and the command defining it.
+#[derive(Clone, Debug)]
+struct Global {
+    id: String,
+    cmd_chain: String,
+    anchor: String,
+}
+
+pub(crate) fn extract_with(command: &Command, settings: &Settings) -> DocPage {
+    // Build the tree like clap does before parsing, so that global arguments
+    // are propagated and the help and version flags are generated
+    let mut command = command.clone();
+    command.build();
+
+    // Anchors are handed out in the order of the page, so they are stable, and
+    // never clash with the IDs of the templates, e.g. `search-index`
+    let mut slugger = Slugger::reserving(TEMPLATE_IDS);
+    let fmt_command = fmt_cmd(&command, Vec::new(), &[], settings, &mut slugger);
+
+    let mut children_commands: Vec<DocCommand> = Vec::new();
+    let parents: Vec<String> = Vec::new();
+    let children = extract_subcommands(
+        &command,
+        &fmt_command.anchor,
+        &mut children_commands,
+        parents,
+        Vec::new(),
+        settings,
+        &mut slugger,
+    );
+
+    let tree = DocTreeNode {
+        name: fmt_command.title.clone(),
+        cmd_chain: fmt_command.cmd_chain.clone(),
+        anchor: fmt_command.anchor.clone(),
+        children,
+    };
+
+    DocPage {
+        main: fmt_command,
+        subcommands: children_commands,
+        tree,
+    }
+}
+
+/// Pick the help text for `verbosity`, falling back to the other one.
+fn help_text(short: Option<&StyledStr>, long: Option<&StyledStr>, verbosity: Verbosity) -> String {
+    match pick(short, long, verbosity) {
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
+
+/// Pick the short or long variant of a text for `verbosity`, falling back to
+/// the other one.
+fn pick<T>(short: Option<T>, long: Option<T>, verbosity: Verbosity) -> Option<T> {
+    match verbosity {
+        Verbosity::Summary => short.or(long),
+        Verbosity::Full => long.or(short),
+    }
+}
+
+fn fmt_cmd(
+    command: &Command,
+    parents: Vec<String>,
+    globals: &[Global],
+    settings: &Settings,
+    slugger: &mut Slugger,
+) -> DocCommand {
+    let verbosity = settings.verbosity;
+    let description = help_text(command.get_about(), command.get_long_about(), verbosity);
+
+    let mut ancestors = parents.clone();
+    ancestors.push(command.get_name().to_string());
+    let cmd_chain = ancestors.join(" ");
+    let anchor = slugger.command(&cmd_chain);
+
+    let mut arguments: Vec<DocArg> = Vec::new();
+    let mut options: Vec<DocArg> = Vec::new();
+    let mut sections: Vec<DocSection> = Vec::new();
+    let mut inherited: Vec<DocInheritedArg> = Vec::new();
+    let mut conflicts = get_conflicts(command);
+    for arg in command.get_arguments() {
+        // Ignore the arguments that are hidden
+        if arg.is_hide_set() {
+            continue;
+        }
+        if !settings.help_flags && is_help_flag(arg) {
+            continue;
+        }
+
+        let name = match (arg.get_long(), arg.get_short()) {
+            (Some(long), _) => long.to_string(),
+            (None, Some(short)) => short.to_string(),
+            (None, None) => arg.get_id().to_string(),
+        };
+        let (min_values, max_values) = get_num_values(arg);
+        let fmt_arg = DocArg {
+            flags: fmt_flags(arg),
+            aliases: get_arg_aliases(arg, settings),
+            anchor: slugger.arg(&anchor, &name),
+            description: help_text(arg.get_help(), arg.get_long_help(), settings.verbosity),
+            default_values: get_default_values(arg),
+            possible_values: get_possible_values(arg),
+            env: get_env(arg),
+            value_delimiter: arg.get_value_delimiter(),
+            min_values,
+            max_values,
+            value_count: value_count(min_values, max_values),
+            conflicts_with: conflicts.remove(arg.get_id().as_str()).unwrap_or_default(),
+        };
+
+        if let Some(global) = globals.iter().find(|t| t.id == arg.get_id().as_str()) {
+            inherited.push(DocInheritedArg {
+                arg: fmt_arg,
+                cmd_chain: global.cmd_chain.clone(),
+                anchor: global.anchor.clone(),
+            });
+            continue;
+        }
+
+        // Like `--help`, arguments with a heading are listed under it, whether
+        // they are positional or not
+        match arg.get_help_heading() {
+            Some(heading) => match sections.iter_mut().find(|t| t.heading == heading) {
+                Some(section) => section.args.push(fmt_arg),
+                None => sections.push(DocSection {
+                    heading: heading.to_string(),
+                    args: vec![fmt_arg],
+                }),
+            },
+            None if arg.is_positional() => arguments.push(fmt_arg),
+            None => options.push(fmt_arg),
+        }
+    }
+
+    // Format the subcommands
+    let mut subcommands: Vec<DocSubcommand> = Vec::new();
+    for subcommand in documented_subcommands(command, settings) {
+        subcommands.push(DocSubcommand {
+            name: subcommand.get_name().to_string(),
+            flags: fmt_cmd_flags(subcommand),
+            aliases: get_cmd_aliases(subcommand, settings),
+            description: help_text(subcommand.get_about(), subcommand.get_long_about(), Verbosity::Summary),
+        });
+    }
+
+    let groups = command
+        .get_groups()
+        .filter_map(|group| fmt_group(command, group))
+        .collect();
+
+    let usages = usage::usages(command, &ancestors[1..]);
+    // The first line without the command chain, for the templates showing it
+    // after the chain
+    let usage = usages.first().map(usage::without_chain).unwrap_or_default();
+
+    DocCommand {
+        title: command.get_name().to_string(),
+        flags: fmt_cmd_flags(command),
+        aliases: get_cmd_aliases(command, settings),
+        usage,
+        usages,
+        cmd_chain,
+        anchor,
+        description,
+        version: pick(command.get_version(), command.get_long_version(), verbosity).map(String::from),
+        author: command.get_author().map(String::from),
+        before_help: help_text(command.get_before_help(), command.get_before_long_help(), verbosity),
+        after_help: help_text(command.get_after_help(), command.get_after_long_help(), verbosity),
+        commands_heading: command
+            .get_subcommand_help_heading()
+            .unwrap_or("Commands")
+            .to_string(),
+        commands: subcommands,
+        arguments,
+        options,
+        sections,
+        inherited,
+        groups,
+    }
+}
+
+/*
+ * FLAG BLOCK
+ */
+
+/// Format the signature of `arg` the way `--help` does, e.g. `-f, --file <FILE>`,
+/// `--color[=<WHEN>]` or `-- <ARGS>...`.
+fn fmt_flags(arg: &Arg) -> String {
+    // clap displays an argument as its long or short name followed by its
+    // values, with the same rules as `--help` for value names, optional values,
+    // `require_equals` and `num_args`, so only the names are added here
+    let displayed = arg.to_string();
+    let name = match (arg.get_long(), arg.get_short()) {
+        (Some(long), _) => format!("--{}", long),
+        (None, Some(short)) => format!("-{}", short),
+        (None, None) => String::new(),
+    };
+    let values = displayed.strip_prefix(name.as_str()).unwrap_or(&displayed);
+
+    let mut s = match (arg.get_short(), arg.get_long()) {
+        (Some(short), Some(long)) => format!("-{}, --{}", short, long),
+        (Some(short), None) => format!("-{}", short),
+        (None, Some(long)) => format!("--{}", long),
+        (None, None) => String::new(),
+    };
+
+    // Arguments after `--` are shown like in the usage, e.g. `[-- <ARGS>...]`
+    if arg.is_positional() && arg.is_last_set() {
+        let values = arg.clone().required(true).to_string();
+        return match arg.is_required_set() {
+            true => format!("-- {}", values),
+            false => format!("[-- {}]", values),
+        };
+    }
+    s.push_str(values);
+
+    s
+}
+
+/// Other names of `arg`, in the order of `--help`, e.g. `-x` then `--extract`.
+fn get_arg_aliases(arg: &Arg, settings: &Settings) -> Vec<String> {
+    let (shorts, longs) = match settings.hidden_aliases {
+        true => (arg.get_all_short_aliases(), arg.get_all_aliases()),
+        false => (arg.get_visible_short_aliases(), arg.get_visible_aliases()),
+    };
+
+    let shorts = shorts.unwrap_or_default().into_iter().map(|t| format!("-{}", t));
+    let longs = longs.unwrap_or_default().into_iter().map(|t| format!("--{}", t));
+    shorts.chain(longs).collect()
+}
+
+fn get_default_values(arg: &Arg) -> Vec<String> {
+    // Flags have an implicit `false` default that clap does not show either
+    if !arg.get_action().takes_values() || arg.is_hide_default_value_set() {
+        return Vec::new();
+    }
+
+    arg.get_default_values()
+        .iter()
+        .map(|value| value.to_string_lossy().to_string())
+        .collect()
+}
+
+fn get_possible_values(arg: &Arg) -> Vec<DocPossibleValue> {
+    if !arg.get_action().takes_values() || arg.is_hide_possible_values_set() {
+        return Vec::new();
+    }
+
+    arg.get_value_parser()
+        .possible_values()
+        .into_iter()
+        .flatten()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| DocPossibleValue {
+            name: value.get_name().to_string(),
+            description: match value.get_help() {
+                Some(help) => help.to_string(),
+                None => String::new(),
+            },
+        })
+        .collect()
+}
+
+fn get_env(arg: &Arg) -> Option<String> {
+    // Only the name is documented, the current value belongs to the machine
+    // generating the documentation
+    match arg.is_hide_env_set() {
+        true => None,
+        false => arg.get_env().map(|env| env.to_string_lossy().to_string()),
+    }
+}
+
+/// How many values `arg` takes each time it's used, `None` for no limit.
+fn get_num_values(arg: &Arg) -> (usize, Option<usize>) {
+    // `num_args` is only set once the command is built, and then defaults to
+    // one value for arguments taking some
+    let range = match arg.get_num_args() {
+        Some(range) => range,
+        None if arg.get_action().takes_values() => ValueRange::SINGLE,
+        None => ValueRange::EMPTY,
+    };
+    let max = match range.max_values() {
+        usize::MAX => None,
+        max => Some(max),
+    };
+
+    (range.min_values(), max)
+}
+
+/// Describe how many values an argument takes, so every backend words it the
+/// same way. Flags and arguments taking a single value need no description.
+fn value_count(min: usize, max: Option<usize>) -> String {
+    let values = |count: usize| match count {
+        1 => "1 value".to_string(),
+        count => format!("{} values", count),
+    };
+    match (min, max) {
+        (0, Some(0)) | (1, Some(1)) => String::new(),
+        (min, Some(max)) if min == max => format!("Takes {}", values(min)),
+        (0, Some(max)) => format!("Takes at most {}", values(max)),
+        (min, Some(max)) => format!("Takes {} to {} values", min, max),
+        (0, None) => "Takes any number of values".to_string(),
+        (min, None) => format!("Takes at least {}", values(min)),
+    }
+}
+
+/*
+ * COMMAND NAMES BLOCK
+ */
+
+/// Format the flags a pacman-style subcommand is called with, e.g. `-S, --sync`.
+fn fmt_cmd_flags(command: &Command) -> String {
+    let short = command.get_short_flag().map(|t| format!("-{}", t));
+    let long = command.get_long_flag().map(|t| format!("--{}", t));
+    short.into_iter().chain(long).collect::<Vec<String>>().join(", ")
+}
+
+/// Other names of `command`, in the order of `--help`: short flags, long
+/// flags, then names, e.g. `-s`, `--synchronize` and `sy`.
+fn get_cmd_aliases(command: &Command, settings: &Settings) -> Vec<String> {
+    let (shorts, longs, names): (Vec<char>, Vec<&str>, Vec<&str>) = match settings.hidden_aliases {
+        true => (
+            command.get_all_short_flag_aliases().collect(),
+            command.get_all_long_flag_aliases().collect(),
+            command.get_all_aliases().collect(),
+        ),
+        false => (
+            command.get_visible_short_flag_aliases().collect(),
+            command.get_visible_long_flag_aliases().collect(),
+            command.get_visible_aliases().collect(),
+        ),
+    };
+
+    let shorts = shorts.into_iter().map(|t| format!("-{}", t));
+    let longs = longs.into_iter().map(|t| format!("--{}", t));
+    shorts.chain(longs).chain(names.into_iter().map(String::from)).collect()
+}
+
+/*
+ * RELATIONSHIP BLOCK
+ */
+
+fn fmt_group(command: &Command, group: &ArgGroup) -> Option<DocGroup> {
+    let multiple = group.clone().is_multiple();
+    // Optional groups that accept any number of arguments don't constrain
+    // anything, and clap derive creates one for every `Args` struct
+    if multiple && !group.is_required_set() {
+        return None;
+    }
+
+    let args = group
+        .get_args()
+        .filter(|id| !find_arg(command, id.as_str()).is_some_and(Arg::is_hide_set))
+        .map(|id| display_name(command, id.as_str()))
+        .collect::<Vec<String>>();
+    if args.is_empty() {
+        return None;
+    }
+
+    let required = group.is_required_set();
+    Some(DocGroup {
+        name: group.get_id().to_string(),
+        args,
+        required,
+        multiple,
+        rule: group_rule(required, multiple).to_string(),
+    })
+}
+
+/// Describe how the arguments of a group can be used together, so every
+/// backend words it the same way.
+fn group_rule(required: bool, multiple: bool) -> &'static str {
+    match (required, multiple) {
+        (true, true) => "At least one of these is required",
+        (true, false) => "Exactly one of these is required",
+        (false, _) => "At most one of these can be used",
+    }
+}
+
+/// Every argument of `command` each argument can't be used with, whichever
+/// side declared the conflict, by id.
+fn get_conflicts(command: &Command) -> HashMap<String, Vec<String>> {
+    let declared: HashMap<&str, Vec<String>> = command
+        .get_arguments()
+        .map(|arg| (arg.get_id().as_str(), conflict_ids(command, arg)))
+        .collect();
+
+    let mut conflicts: HashMap<String, Vec<String>> = HashMap::new();
+    for arg in command.get_arguments() {
+        let id = arg.get_id().as_str();
+        let mut names = Vec::new();
+        for other in command.get_arguments() {
+            let other_id = other.get_id().as_str();
+            if other.is_hide_set() || other_id == id {
+                continue;
+            }
+            if declared[id].iter().any(|t| t == other_id) || declared[other_id].iter().any(|t| t == id) {
+                names.push(display_name(command, other_id));
+            }
+        }
+        conflicts.insert(id.to_string(), names);
+    }
+
+    conflicts
+}
+
+/// Ids of the arguments `arg` declares a conflict with, groups being replaced
+/// by their arguments.
+///
+/// `Command::get_arg_conflicts_with` panics when a global argument conflicts
+/// with a group, since it only looks for arguments then. clap checks that
+/// the conflicts of a global argument exist in every command it is
+/// propagated to, so it is looked up as a local argument of `command`.
+fn conflict_ids(command: &Command, arg: &Arg) -> Vec<String> {
+    let conflicts = match arg.is_global_set() {
+        true => command.get_arg_conflicts_with(&arg.clone().global(false)),
+        false => command.get_arg_conflicts_with(arg),
+    };
+
+    conflicts.iter().map(|t| t.get_id().to_string()).collect()
+}
+
+fn find_arg<'a>(command: &'a Command, id: &str) -> Option<&'a Arg> {
+    command.get_arguments().find(|arg| arg.get_id() == id)
+}
+
+/// Name an argument the way users type it, e.g. `--file` or `<PATH>`.
+fn display_name(command: &Command, id: &str) -> String {
+    let arg = match find_arg(command, id) {
+        Some(arg) => arg,
+        None => return id.to_string(),
+    };
+
+    if let Some(long) = arg.get_long() {
+        return format!("--{}", long);
+    }
+    if let Some(short) = arg.get_short() {
+        return format!("-{}", short);
+    }
+    match arg.get_value_names() {
+        Some([name, ..]) => format!("<{}>", name),
+        _ => format!("<{}>", id),
+    }
+}
+
+/// Document the subcommands of `subcommand`, anchored at `anchor`, and of all
+/// their descendants. Returns the tree of the subcommands.
+fn extract_subcommands(
+    subcommand: &Command,
+    anchor: &str,
+    children_commands: &mut Vec<DocCommand>,
+    parents: Vec<String>,
+    globals: Vec<Global>,
+    settings: &Settings,
+    slugger: &mut Slugger,
+) -> Vec<DocTreeNode> {
+    let mut parents: Vec<String> = parents;
+    parents.push(subcommand.get_name().to_string());
+
+    // Global arguments defined here are inherited by every subcommand below
+    let mut globals = globals;
+    for arg in subcommand.get_arguments().filter(|t| t.is_global_set()) {
+        if !globals.iter().any(|t| t.id == arg.get_id().as_str()) {
+            globals.push(Global {
+                id: arg.get_id().to_string(),
+                cmd_chain: parents.join(" "),
+                anchor: anchor.to_string(),
+            });
+        }
+    }
+
+    let mut tree = Vec::new();
+    for child in documented_subcommands(subcommand, settings) {
+        let mut fmt_command = fmt_cmd(child, parents.clone(), &globals, settings, slugger);
+        let mut node = DocTreeNode {
+            name: fmt_command.title.clone(),
+            cmd_chain: fmt_command.cmd_chain.clone(),
+            anchor: fmt_command.anchor.clone(),
+            children: Vec::new(),
+        };
+
+        // The generated help subcommand mirrors the whole tree, which is
+        // already documented
+        if is_help_subcommand(subcommand, child) {
+            fmt_command.commands.clear();
+            children_commands.push(fmt_command);
+            tree.push(node);
+            continue;
+        }
+
+        children_commands.push(fmt_command);
+        node.children = extract_subcommands(
+            child,
+            &node.anchor,
+            children_commands,
+            parents.clone(),
+            globals.clone(),
+            settings,
+            slugger,
+        );
+        tree.push(node);
+    }
+
+    tree
+}
+
+fn documented_subcommands<'a>(command: &'a Command, settings: &'a Settings) -> impl Iterator<Item = &'a Command> {
+    command
+        .get_subcommands()
+        .filter(move |t| settings.help_flags || !is_help_subcommand(command, t))
+}
+
+/// Whether `arg` is one of the `--help` and `--version` flags clap generates.
+fn is_help_flag(arg: &Arg) -> bool {
+    matches!(
+        arg.get_action(),
+        ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+    )
+}
+
+/// Whether `subcommand` is the `help` subcommand clap generates for `parent`,
+/// which is always the last one.
+fn is_help_subcommand(parent: &Command, subcommand: &Command) -> bool {
+    !parent.is_disable_help_subcommand_set()
+        && subcommand.get_name() == "help"
+        && parent
+            .get_subcommands()
+            .last()
+            .is_some_and(|last| std::ptr::eq(last, subcommand))
+}
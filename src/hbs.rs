@@ -0,0 +1,171 @@
+//! Handlebars implementation of [`Renderer`].
+
+use handlebars::Handlebars;
+
+use crate::{html, page, site, theme, Error, DocCommand, DocPage, Renderer, Result, SitePage};
+
+static TEMPLATE_FILE: &str = include_str!("../data/template.html");
+static CODE_PARTIAL: &str = include_str!("../data/usage-partial.html");
+static ARG_PARTIAL: &str = include_str!("../data/arg-details.html");
+static SEARCH_PARTIAL: &str = include_str!("../data/search.html");
+static NAV_PARTIAL: &str = include_str!("../data/nav.html");
+static SITE_PAGE_FILE: &str = include_str!("../data/site-page.html");
+static SITE_INDEX_FILE: &str = include_str!("../data/site-index.html");
+
+/// Bundled templates and partials, by name.
+static DEFAULT_TEMPLATES: [(&str, &str); 8] = [
+    ("template", TEMPLATE_FILE),
+    ("usage-partial", CODE_PARTIAL),
+    ("arg-details", ARG_PARTIAL),
+    ("style", theme::DEFAULT_STYLE),
+    ("search", SEARCH_PARTIAL),
+    ("nav", NAV_PARTIAL),
+    ("site-page", SITE_PAGE_FILE),
+    ("site-index", SITE_INDEX_FILE),
+];
+
+/// Render the documentation with [Handlebars](https://docs.rs/handlebars) templates.
+///
+/// Besides the built-in helpers, templates can use `paragraph`, which escapes
+/// a help text and formats its paragraphs, lists, code blocks and links as
+/// HTML, and `anchor`, which turns a command chain into an anchor name. The
+/// `anchor` fields of the model are preferred over the helper, since they are
+/// unique and only contain URL safe characters.
+#[derive(Debug)]
+pub struct HandlebarsRenderer {
+    handlebars: Handlebars<'static>,
+}
+
+impl HandlebarsRenderer {
+    /// Create a renderer with the bundled templates and partials.
+    pub fn new() -> Result<Self> {
+        let mut handlebars = Handlebars::new();
+
+        handlebars.register_helper("paragraph", Box::new(paragraph));
+        handlebars.register_helper("anchor", Box::new(anchor));
+
+        let mut renderer = HandlebarsRenderer { handlebars };
+        for (name, source) in DEFAULT_TEMPLATES {
+            renderer.register(name, source)?;
+        }
+
+        Ok(renderer)
+    }
+
+    /// Write the help texts formatted by `paragraph` as they are, without
+    /// escaping them. Defaults to `false`.
+    ///
+    /// Only enable this when every help text of the command is trusted HTML.
+    pub fn trusted_html(&mut self, trusted: bool) {
+        match trusted {
+            true => self.handlebars.register_helper("paragraph", Box::new(trusted_paragraph)),
+            false => self.handlebars.register_helper("paragraph", Box::new(paragraph)),
+        }
+    }
+
+    /// Register `source` as the template or partial called `name`, replacing
+    /// the bundled one if any.
+    pub fn register(&mut self, name: &str, source: &str) -> Result<()> {
+        self.handlebars
+            .register_template_string(name, source)
+            .map_err(|err| Error::template(name, err))
+    }
+
+    /// Find the command chain responsible for a failed render.
+    ///
+    /// The page is rendered again with the main command alone, then with one
+    /// subcommand at a time, and the first one that fails is reported. Since this
+    /// only runs after a failure, the extra renders don't matter.
+    fn locate_failure(&self, page: &DocPage) -> String {
+        let single = |subcommands: Vec<DocCommand>| DocPage {
+            main: page.main.clone(),
+            subcommands,
+            tree: page.tree.clone(),
+        };
+
+        let fails = |page: &DocPage| match page::single_page(page) {
+            Ok(data) => self.handlebars.render("template", &data).is_err(),
+            Err(_) => true,
+        };
+
+        if fails(&single(Vec::new())) {
+            return page.main.cmd_chain.clone();
+        }
+
+        page.subcommands
+            .iter()
+            .find(|t| fails(&single(vec![(*t).clone()])))
+            .unwrap_or(&page.main)
+            .cmd_chain
+            .clone()
+    }
+}
+
+impl Renderer for HandlebarsRenderer {
+    fn render_page(&self, page: &DocPage) -> Result<String> {
+        let data = page::single_page(page)?;
+        self.handlebars.render("template", &data).map_err(|err| {
+            let command = self.locate_failure(page);
+            Error::render(&command, err)
+        })
+    }
+
+    fn render_site(&self, page: &DocPage) -> Result<Vec<SitePage>> {
+        site::render(
+            page,
+            |index| {
+                self.handlebars
+                    .render("site-index", index)
+                    .map_err(|err| Error::render(&page.main.cmd_chain, err))
+            },
+            |data| {
+                self.handlebars
+                    .render("site-page", data)
+                    .map_err(|err| Error::render(&data.command.command.cmd_chain, err))
+            },
+        )
+    }
+}
+
+/// Implement a custom handlebar function that escapes the text and formats it
+/// as <p>, <ul> and <pre> blocks
+/// This allows for proper paragraph inside the HTML so short and long descriptions
+/// can be respected.
+fn paragraph(h: &handlebars::Helper, _: &Handlebars, _: &handlebars::Context, _rc: &mut handlebars::RenderContext, out: &mut dyn handlebars::Output) -> handlebars::HelperResult {
+    write_paragraph(h, out, false)
+}
+
+/// Same as [`paragraph`], for help texts that are trusted HTML.
+fn trusted_paragraph(h: &handlebars::Helper, _: &Handlebars, _: &handlebars::Context, _rc: &mut handlebars::RenderContext, out: &mut dyn handlebars::Output) -> handlebars::HelperResult {
+    write_paragraph(h, out, true)
+}
+
+fn write_paragraph(h: &handlebars::Helper, out: &mut dyn handlebars::Output, trusted: bool) -> handlebars::HelperResult {
+    let param = h
+        .param(0)
+        .ok_or(handlebars::RenderErrorReason::ParamNotFoundForIndex("paragraph", 0))?;
+    let param = param.value().as_str().unwrap_or_default();
+    let param = html::help_text(param, trusted);
+
+    out.write(param.as_str())?;
+    Ok(())
+}
+
+/// Implement a custom handlebar function that replaces spaces for dashes
+/// This allows for better styled anchors
+fn anchor(
+    h: &handlebars::Helper,
+    _: &Handlebars,
+    _: &handlebars::Context,
+    _rc: &mut handlebars::RenderContext,
+    out: &mut dyn handlebars::Output
+) -> handlebars::HelperResult {
+    let param = h
+        .param(0)
+        .ok_or(handlebars::RenderErrorReason::ParamNotFoundForIndex("anchor", 0))?;
+    let param = param.value().as_str().unwrap_or_default();
+    let param = html::escape(&param.replace(" ", "-"));
+
+    out.write(param.as_str())?;
+    Ok(())
+}
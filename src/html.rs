@@ -0,0 +1,74 @@
+//! HTML helpers shared by the template engines.
+
+use crate::text::{self, Block, Span};
+
+/// Escape the characters of `text` that have a meaning in HTML, so help texts
+/// like `Vec<T>` or `<FILE>` are displayed as written.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#x27;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Format a help text as HTML: paragraphs, lists, code blocks and links.
+///
+/// A help text made of a single paragraph is written without a `<p>` tag, so
+/// it fits anywhere, e.g. in a list item. The text is escaped first, unless it
+/// is `trusted` to be HTML already, in which case it is not searched for links
+/// either.
+pub(crate) fn help_text(text: &str, trusted: bool) -> String {
+    let blocks = text::blocks(text);
+    if let [Block::Paragraph(text)] = blocks.as_slice() {
+        return inline(text, trusted);
+    }
+
+    let mut out = String::new();
+    for block in &blocks {
+        match block {
+            Block::Paragraph(text) => {
+                out.push_str(&format!("<p>{}</p>", inline(text, trusted)));
+            }
+            Block::List(items) => {
+                out.push_str("<ul>");
+                for item in items {
+                    out.push_str(&format!("<li>{}</li>", inline(item, trusted)));
+                }
+                out.push_str("</ul>");
+            }
+            Block::Code(lines) => {
+                let code = lines.join("\n");
+                let code = match trusted {
+                    true => code,
+                    false => escape(&code),
+                };
+                out.push_str(&format!("<pre><code>{}</code></pre>", code));
+            }
+        }
+    }
+
+    out
+}
+
+/// Text of a paragraph or list item, with its URLs turned into links.
+fn inline(text: &str, trusted: bool) -> String {
+    if trusted {
+        return text.to_string();
+    }
+
+    text::spans(text)
+        .into_iter()
+        .map(|span| match span {
+            Span::Text(text) => escape(text),
+            Span::Link(url) => format!("<a href=\"{0}\">{0}</a>", escape(url)),
+        })
+        .collect()
+}
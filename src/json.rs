@@ -0,0 +1,32 @@
+//! JSON backend.
+//!
+//! Serializes the whole [`DocPage`] next to a `schema_version`, following the
+//! JSON Schema shipped in `data/schema.json`.
+
+use serde_derive::Serialize;
+
+use crate::DocPage;
+
+/// Version of the JSON format produced by [`render_json`](crate::render_json).
+///
+/// It is bumped whenever a property is removed, renamed or changes type. New
+/// properties can be added without bumping it.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// JSON Schema of the format produced by [`render_json`](crate::render_json).
+pub const JSON_SCHEMA: &str = include_str!("../data/schema.json");
+
+#[derive(Serialize)]
+struct JsonPage<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    page: &'a DocPage,
+}
+
+/// Render `page` as pretty-printed JSON.
+pub(crate) fn render(page: &DocPage) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&JsonPage {
+        schema_version: SCHEMA_VERSION,
+        page,
+    })
+}
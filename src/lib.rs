@@ -1,7 +1,13 @@
 //! Generate documentation for clap command-line tools
 //!
 //! It outputs a single HTML page with all the commands, arguments and flags that
-//! the application uses.
+//! the application uses. The same documentation can be rendered as Markdown
+//! with [`render_markdown`], as one man page per command with
+//! [`render_man_pages`], or as a static site with one HTML page per command
+//! with [`write_site`]. The whole command tree can also be exported as JSON
+//! with [`render_json`], following the [`JSON_SCHEMA`]. Use [`ClapShow`] to
+//! replace the bundled templates and partials, and [`extract`] to get the
+//! documentation model itself.
 //!
 //! It will use the long help when available, otherwise it will use the
 //! short help.
@@ -9,290 +15,191 @@
 //! # Example
 //!
 //! ```
+//! use clap::{CommandFactory, Parser, Subcommand};
+//!
 //! /// This explains how the application works on details. Probably a good to
 //! /// have an introduction to the commands and the purpose of it.
 //! #[derive(Parser)]
 //! struct Cli {
-//!     command: Commands
+//!     #[command(subcommand)]
+//!     command: Commands,
 //! }
 //!
-//! #[derive(subcommand)]
-//! enum commands {}
-//!
-//! fn main() {
-//!     let cli = Cli::parse();
-//!     let command = Cli::command();
-//!     clap_show::help_command(&command)
+//! #[derive(Subcommand)]
+//! enum Commands {
+//!     /// Deploy the application
+//!     Deploy,
 //! }
+//!
+//! let command = Cli::command();
+//! # #[cfg(any(feature = "handlebars", feature = "ramhorns"))] {
+//! let html = clap_show::render_help(&command)?;
+//! assert!(html.contains("deploy"));
+//! # }
+//! # Ok::<(), clap_show::Error>(())
 //! ```
 
-static TEMPLATE_FILE: &'static str = include_str!("../data/template.html");
-static CODE_PARTIAL: &'static str = include_str!("../data/usage-partial.html");
-
-use clap::{Arg, Command};
-use handlebars::Handlebars;
-use serde_derive::Serialize;
-
-#[derive(Serialize, Clone, Debug)]
-struct FmtArg {
-    flags: String,
-    description: String,
-}
-
-#[derive(Serialize, Clone, Debug)]
-struct FmtCmd {
-    name: String,
-    description: String,
-}
-
-#[derive(Serialize, Clone, Debug)]
-struct FmtCommands {
-    title: String,
-    usage: String,
-    cmd_chain: String,
-    description: String,
-    commands: Vec<FmtCmd>,
-    arguments: Vec<FmtArg>,
-    options: Vec<FmtArg>,
-}
-
-#[derive(Serialize, Clone, Debug)]
-struct Page {
-    main: FmtCommands,
-    subcommands: Vec<FmtCommands>,
-}
-
-/// Format the help information for `command` as Markdown.
+mod builder;
+mod error;
+mod extract;
+#[cfg(feature = "handlebars")]
+mod hbs;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+mod html;
+mod json;
+mod man;
+mod markdown;
+mod model;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+mod nav;
+#[cfg(feature = "ramhorns")]
+mod mustache;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+mod page;
+mod renderer;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+mod search;
+mod site;
+mod slug;
+mod text;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+mod theme;
+mod usage;
+
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+pub use builder::ClapShow;
+pub use error::{Error, Result};
+pub use extract::{extract, Verbosity};
+#[cfg(feature = "handlebars")]
+pub use hbs::HandlebarsRenderer;
+pub use json::{JSON_SCHEMA, SCHEMA_VERSION};
+pub use man::ManPage;
+pub use model::{
+    DocArg, DocCommand, DocGroup, DocInheritedArg, DocPage, DocPossibleValue, DocSection,
+    DocSubcommand, DocTreeNode, DocUsage, DocUsageToken, DocUsageTokenKind,
+};
+#[cfg(feature = "ramhorns")]
+pub use mustache::RamhornsRenderer;
+pub use renderer::{Engine, Renderer};
+pub use site::SitePage;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+pub use theme::Theme;
+
+/// Format the help information for `command` as HTML.
 ///
 /// Output is printed to the standard output, using [`println!`].
-pub fn write_help_factory<C: clap::CommandFactory>() {
+pub fn write_help_factory<C: clap::CommandFactory>() -> Result<()> {
     let command = C::command();
 
-    help_command(&command);
+    help_command(&command)
 }
 
-/// Format the help information for `command` as Markdown.
+/// Format the help information for `command` as HTML.
 ///
 /// Output is printed to the standard output, using [`println!`].
-pub fn help_command(command: &clap::Command) {
-    build_cmd(command);
-}
-
-fn get_usage(command: &mut Command) -> String {
-    let parts = command.render_usage().to_string();
-    let parts = parts.split(" ").collect::<Vec<&str>>();
-    let slice = parts[2..]
-        .iter()
-        .map(|v| v.to_string())
-        .collect::<Vec<String>>();
-    slice.join(" ").to_string()
+pub fn help_command(command: &clap::Command) -> Result<()> {
+    println!("{}", render_help(command)?);
+    Ok(())
 }
 
-fn fmt_cmd(command: &Command, parents: Vec<String>) -> FmtCommands {
-    let description = match command.get_long_about() {
-        Some(value) => value.to_string(),
-        None => match command.get_about() {
-            Some(value) => value.to_string(),
-            None => String::new()
-        },
-    };
-
-    let mut arguments: Vec<FmtArg> = Vec::new();
-    let mut options: Vec<FmtArg> = Vec::new();
-    for arg in command.get_arguments() {
-        // Ignore the arguments that are hidden
-        if arg.is_hide_set() {
-            continue;
-        }
-
-        let fmt_arg = FmtArg {
-            flags: fmt_flags(&arg),
-            description: match arg.get_help_heading() {
-                Some(value) => value.to_string(),
-                None => match arg.get_long_help() {
-                    Some(value) => value.to_string(),
-                    None => String::new(),
-                },
-            },
-        };
-
-        if arg.is_positional() {
-            arguments.push(fmt_arg);
-        } else {
-            options.push(fmt_arg);
-        }
-    }
-
-    // Format the subcommands
-    let mut subcommands: Vec<FmtCmd> = Vec::new();
-    for command in command.get_subcommands() {
-        subcommands.push(FmtCmd {
-            name: command.get_name().to_string(),
-            description: match command.get_about() {
-                Some(t) => t.to_string(),
-                _ => String::new(),
-            },
-        });
-    }
-
-    let mut cmd = command.clone();
-    let usage = get_usage(&mut cmd);
-
-    let mut ancestors = parents.clone();
-    ancestors.push(command.get_name().to_string());
+/// Render the help information for `C` as an HTML document.
+pub fn render_help_factory<C: clap::CommandFactory>() -> Result<String> {
+    let command = C::command();
 
-    FmtCommands {
-        title: command.get_name().to_string(),
-        usage,
-        cmd_chain: ancestors.join(" "),
-        description,
-        commands: subcommands,
-        arguments,
-        options,
-    }
+    render_help(&command)
 }
 
-/**
- * FLAG BLOCK
- */
-
-fn fmt_flags(arg: &Arg) -> String {
-    let short = match arg.get_short() {
-        Some(value) => format!("-{}", value),
-        None => String::new(),
-    };
-    let long = match arg.get_long() {
-        Some(value) => format!("--{}", value),
-        None => String::new(),
-    };
-    let mut values = match arg.get_action().takes_values() {
-        true => match arg.get_value_names() {
-            // TODO: What if multiple names are provided?
-            Some([]) => Vec::new(),
-            Some(value) => value
-                .iter()
-                .map(|f| {
-                    match arg.is_required_set() {
-                        true => format!("<{}>", f),
-                        false  => format!("[{}]", f)
-                    }
-                })
-                .collect::<Vec<String>>(),
-            None => vec![format!("<{}>", arg.get_id())],
-        },
-        false => Vec::new(),
-    };
-
-    // Check if the argument takes multiple values
-    let num_vals = arg.get_num_args().unwrap_or_else(|| 1.into());
-    if num_vals.max_values() > 1 {
-        values.push("...".to_string());
-    }
-
-    // print short arg, and add a comma if a long arg exists
-    let mut s = format!("{:min$}", short, min = 2);
-
-    if long.len() > 0 {
-        // Add a comma if there is a long arg, otherwise just a space
-        if short.len() > 0 {
-            s.push_str(", ");
-        } else {
-            s.push_str("  ");
-        }
-        s.push_str(format!("{}", long).as_str());
-    };
-
-    if values.len() > 0 {
-        s.push_str(format!(" {}", values.join(" ")).as_str());
-    }
-
-    return s;
+/// Render the help information for `command` as an HTML document.
+///
+/// The whole page is returned as a [`String`] so it can be written to a file,
+/// embedded somewhere else or post-processed.
+pub fn render_help(command: &clap::Command) -> Result<String> {
+    ClapShow::new(command).render()
 }
 
-fn build_cmd(command: &Command) -> &Command {
-    let hc = &command;
-    let fmt_command = fmt_cmd(&hc, Vec::new());
-
-    let mut children_commands: Vec<FmtCommands> = Vec::new();
-    let parents: Vec<String> = Vec::new();
-    extract_subcommands(command, &mut children_commands, parents);
-
-    let mut handlebars = Handlebars::new();
-
-    handlebars.register_helper("paragraph", Box::new(paragraph));
-    handlebars.register_helper("anchor", Box::new(anchor));
-
-    handlebars
-        .register_template_string("template", TEMPLATE_FILE)
-        .expect("Unable to load base template");
-    handlebars
-        .register_template_string("usage-partial", CODE_PARTIAL)
-        .expect("Unable to load base template");
-
-    println!(
-        "{}",
-        handlebars
-            .render(
-                "template",
-                &Page {
-                    main: fmt_command,
-                    subcommands: children_commands
-                }
-            )
-            .unwrap()
-    );
-
-    hc
+/// Write the help information for `command` as HTML into an [`io::Write`] sink.
+pub fn write_help<W: io::Write>(command: &clap::Command, writer: &mut W) -> Result<()> {
+    ClapShow::new(command).write(writer)
 }
 
-fn extract_subcommands(
-    subcommand: &Command,
-    children_commands: &mut Vec<FmtCommands>,
-    parents: Vec<String>,
-) {
-    let mut parents: Vec<String> = parents;
-    parents.push(subcommand.get_name().to_string());
+/// Write the help information for `command` as HTML into a [`fmt::Write`] sink.
+pub fn write_help_fmt<W: fmt::Write>(command: &clap::Command, writer: &mut W) -> Result<()> {
+    writer.write_str(&render_help(command)?)?;
+    Ok(())
+}
 
-    for subcommand in subcommand.get_subcommands() {
-        let subcommand = subcommand.to_owned();
-        children_commands.push(fmt_cmd(&subcommand, parents.clone()));
+/// Render the help information for `command` as a Markdown document.
+///
+/// Every subcommand gets its own heading, linked from a table of contents at
+/// the top of the document.
+pub fn render_markdown(command: &clap::Command) -> Result<String> {
+    ClapShow::new(command).render_markdown()
+}
 
-        extract_subcommands(&subcommand, children_commands, parents.clone());
-    }
+/// Write the help information for `command` as Markdown into an [`io::Write`] sink.
+pub fn write_markdown<W: io::Write>(command: &clap::Command, writer: &mut W) -> Result<()> {
+    writer.write_all(render_markdown(command)?.as_bytes())?;
+    Ok(())
 }
 
-/// Implement a custom handlebar function that replaces "\n" for <br /> tags
-/// This allows for proper paragraph inside the HTML so short and long descriptions
-/// can be respected.
-fn paragraph (h: &handlebars::Helper, _: &Handlebars, _: &handlebars::Context, _rc: &mut handlebars::RenderContext, out: &mut dyn handlebars::Output) -> handlebars::HelperResult {
-    let param = h.param(0).unwrap();
-    let param = match param.value().as_str() {
-        Some(value) => value,
-        None => "",
-    };
-    let param = param.replace("\n", "<br />");
+/// Render the command tree of `command` as JSON.
+///
+/// The document carries a `schema_version` and follows [`JSON_SCHEMA`].
+///
+/// # Example
+///
+/// ```
+/// use clap::Command;
+///
+/// let command = Command::new("mycli").subcommand(Command::new("deploy"));
+/// let json = clap_show::render_json(&command)?;
+/// let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(value["schema_version"], clap_show::SCHEMA_VERSION);
+/// assert_eq!(value["subcommands"][0]["cmd_chain"], "mycli deploy");
+/// # Ok::<(), clap_show::Error>(())
+/// ```
+pub fn render_json(command: &clap::Command) -> Result<String> {
+    ClapShow::new(command).render_json()
+}
 
-    out.write(param.as_str())?;
+/// Write the command tree of `command` as JSON into an [`io::Write`] sink.
+pub fn write_json<W: io::Write>(command: &clap::Command, writer: &mut W) -> Result<()> {
+    writer.write_all(render_json(command)?.as_bytes())?;
     Ok(())
 }
 
-/// Implement a custom handlebar function that replaces spaces for dashes
-/// This allows for better styled anchors
-fn anchor(
-    h: &handlebars::Helper,
-    _: &Handlebars,
-    _: &handlebars::Context,
-    _rc: &mut handlebars::RenderContext,
-    out: &mut dyn handlebars::Output
-) -> handlebars::HelperResult {
-    let param = h.param(0).unwrap();
-    let param = match param.value().as_str() {
-        Some(value) => value,
-        None => "",
-    };
-    let param = param.replace(" ", "-");
+/// Render one man page for `command` and one for each of its subcommands.
+///
+/// Pages are named after the command chain, e.g. `mycli.1` and `mycli-sub.1`.
+/// Names are lowercase and file system safe, with a `-2`, `-3`... suffix when
+/// two commands would share one.
+pub fn render_man_pages(command: &clap::Command) -> Result<Vec<ManPage>> {
+    ClapShow::new(command).render_man_pages()
+}
+
+/// Write the man pages for `command` into the `dir` directory.
+///
+/// The directory is created when missing. Returns the paths of the written pages.
+pub fn write_man_pages<P: AsRef<Path>>(command: &clap::Command, dir: P) -> Result<Vec<PathBuf>> {
+    ClapShow::new(command).write_man_pages(dir)
+}
 
-    out.write(param.as_str())?;
-    Ok(())
+/// Render a static site for `command`, with one HTML page per command.
+///
+/// The pages are named after the command chain, e.g. `mycli.html` and
+/// `mycli-sub.html`, next to an `index.html` that lists all of them and a
+/// `search-index.js` used by their search box. Names are lowercase and
+/// file system safe, with a `-2`, `-3`... suffix when two commands would share one.
+pub fn render_site(command: &clap::Command) -> Result<Vec<SitePage>> {
+    ClapShow::new(command).render_site()
 }
 
+/// Write the static site for `command` into the `dir` directory.
+///
+/// The directory is created when missing. Returns the paths of the written pages.
+pub fn write_site<P: AsRef<Path>>(command: &clap::Command, dir: P) -> Result<Vec<PathBuf>> {
+    ClapShow::new(command).write_site(dir)
+}
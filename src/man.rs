@@ -0,0 +1,346 @@
+//! Man page (roff) backend.
+//!
+//! Every command in the tree gets its own page: `mycli.1` for the main command
+//! and `mycli-sub.1` for each subcommand, linked together in `SEE ALSO`. Page
+//! names are unique and file system safe, like the pages of the static site.
+
+use std::fmt::{self, Write};
+
+use crate::slug::FileNames;
+use crate::text::{self, Block};
+use crate::{DocArg, DocCommand, DocPage, DocUsage, DocUsageTokenKind};
+
+/// A single rendered man page.
+#[derive(Clone, Debug)]
+pub struct ManPage {
+    /// File name of the page, e.g. `mycli-sub.1`.
+    ///
+    /// Names are unique and only contain lowercase ASCII letters, digits, `_`
+    /// and `-`, whatever the names of the commands.
+    pub name: String,
+    /// Roff source of the page.
+    pub content: String,
+}
+
+/// Render one man page for every command in `page`.
+pub(crate) fn render(page: &DocPage) -> Result<Vec<ManPage>, fmt::Error> {
+    let files = FileNames::new(page, &[]);
+    let mut pages = Vec::new();
+    for data in std::iter::once(&page.main).chain(page.subcommands.iter()) {
+        let mut content = String::new();
+        render_command(data, &files, &mut content)?;
+        pages.push(ManPage {
+            name: format!("{}.1", page_name(&files, &data.cmd_chain)),
+            content,
+        });
+    }
+
+    Ok(pages)
+}
+
+fn render_command(data: &DocCommand, files: &FileNames, out: &mut impl Write) -> fmt::Result {
+    let name = page_name(files, &data.cmd_chain);
+    // The version goes in the footer, as the source of the page, e.g. `mycli 1.2.0`
+    match data.version.as_deref().and_then(|t| t.lines().next()) {
+        Some(version) => writeln!(
+            out,
+            ".TH \"{}\" 1 \"\" \"{}\"",
+            escape(&name.to_uppercase()),
+            escape(&format!("{} {}", data.cmd_chain, version.trim())).replace('"', "\\(dq")
+        )?,
+        None => writeln!(out, ".TH \"{}\" 1", escape(&name.to_uppercase()))?,
+    }
+
+    writeln!(out, ".SH NAME")?;
+    match summary(&data.description) {
+        Some(summary) => writeln!(out, "{} \\- {}", escape(&name), escape(&summary))?,
+        None => writeln!(out, "{}", escape(&name))?,
+    }
+
+    writeln!(out, ".SH SYNOPSIS")?;
+    for (i, line) in data.usages.iter().enumerate() {
+        if i > 0 {
+            writeln!(out, ".br")?;
+        }
+        writeln!(out, "{}", fmt_usage(line))?;
+    }
+
+    // pacman-style flags are listed along with the aliases, like `--help` does
+    let mut names = Vec::new();
+    if !data.flags.is_empty() {
+        names.extend(data.flags.split(", ").map(String::from));
+    }
+    names.extend_from_slice(&data.aliases);
+    if !names.is_empty() {
+        writeln!(out, ".SH ALIASES")?;
+        writeln!(out, "{}", bold_list(&names))?;
+    }
+
+    // The text before the help opens the description, like in `--help`
+    let description = [data.before_help.trim(), data.description.trim()]
+        .iter()
+        .filter(|t| !t.is_empty())
+        .copied()
+        .collect::<Vec<&str>>()
+        .join("\n\n");
+    if !description.is_empty() {
+        writeln!(out, ".SH DESCRIPTION")?;
+        write_paragraphs(out, &description, ".PP")?;
+    }
+
+    write_args(out, "ARGUMENTS", &data.arguments)?;
+    write_args(out, "OPTIONS", &data.options)?;
+    for t in &data.sections {
+        write_args(out, &heading(&t.heading), &t.args)?;
+    }
+
+    if !data.inherited.is_empty() {
+        writeln!(out, ".SH \"INHERITED OPTIONS\"")?;
+        for t in &data.inherited {
+            writeln!(out, ".TP")?;
+            writeln!(out, "{}", fmt_flags(&t.arg.flags))?;
+            write_paragraphs(out, &t.arg.description, ".IP")?;
+            let separate = !t.arg.description.trim().is_empty();
+            write_arg_details(out, &t.arg, separate, files.get(&t.cmd_chain))?;
+        }
+    }
+
+    if !data.groups.is_empty() {
+        writeln!(out, ".SH GROUPS")?;
+        for t in &data.groups {
+            writeln!(out, ".TP")?;
+            writeln!(out, "\\fB{}\\fR", escape(&t.name))?;
+            writeln!(out, "{}: {}.", escape(&t.rule), bold_list(&t.args))?;
+        }
+    }
+
+    if !data.commands.is_empty() {
+        writeln!(out, ".SH {}", heading(&data.commands_heading))?;
+        for t in &data.commands {
+            writeln!(out, ".TP")?;
+            match t.flags.is_empty() {
+                true => writeln!(out, "\\fB{}\\fR", escape(&t.name))?,
+                false => writeln!(out, "\\fB{}\\fR, {}", escape(&t.name), fmt_flags(&t.flags))?,
+            }
+            write_paragraphs(out, &t.description, ".IP")?;
+            if !t.aliases.is_empty() {
+                if !t.description.trim().is_empty() {
+                    writeln!(out, ".IP")?;
+                }
+                writeln!(out, "[aliases: {}]", bold_list(&t.aliases))?;
+            }
+        }
+    }
+
+    if !data.after_help.trim().is_empty() {
+        writeln!(out, ".SH NOTES")?;
+        write_paragraphs(out, &data.after_help, ".PP")?;
+    }
+
+    if let Some(author) = &data.author {
+        writeln!(out, ".SH AUTHORS")?;
+        writeln!(out, "{}", escape(&text::flatten(author)))?;
+    }
+
+    // Link back to the parent command and forward to every child command. The
+    // binary name can have several words, which have no page of their own
+    let mut see_also = Vec::new();
+    if let Some((parent, _)) = data.cmd_chain.rsplit_once(' ') {
+        see_also.extend(files.get(parent));
+    }
+    for t in &data.commands {
+        see_also.extend(files.get(&format!("{} {}", data.cmd_chain, t.name)));
+    }
+    if !see_also.is_empty() {
+        writeln!(out, ".SH \"SEE ALSO\"")?;
+        let refs = see_also
+            .iter()
+            .map(|name| format!("\\fB{}\\fR(1)", escape(name)))
+            .collect::<Vec<String>>();
+        writeln!(out, "{}", refs.join(", "))?;
+    }
+
+    Ok(())
+}
+
+fn write_args(out: &mut impl Write, title: &str, args: &[DocArg]) -> fmt::Result {
+    if args.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, ".SH {}", title)?;
+    for t in args {
+        writeln!(out, ".TP")?;
+        writeln!(out, "{}", fmt_flags(&t.flags))?;
+        write_paragraphs(out, &t.description, ".IP")?;
+        write_arg_details(out, t, !t.description.trim().is_empty(), None)?;
+    }
+
+    Ok(())
+}
+
+/// Write the aliases, default values, possible values, environment variable
+/// and relationships of `arg`, and the page of the command defining it if
+/// inherited.
+///
+/// `separate` tells whether a paragraph was already written for the argument.
+fn write_arg_details(
+    out: &mut impl Write,
+    arg: &DocArg,
+    separate: bool,
+    defined_in: Option<&str>,
+) -> fmt::Result {
+    let mut separate = separate;
+    let mut paragraph = |out: &mut dyn Write| -> fmt::Result {
+        if separate {
+            writeln!(out, ".IP")?;
+        }
+        separate = true;
+        Ok(())
+    };
+
+    if !arg.aliases.is_empty() {
+        paragraph(out)?;
+        writeln!(out, "[aliases: {}]", bold_list(&arg.aliases))?;
+    }
+    if !arg.default_values.is_empty() {
+        paragraph(out)?;
+        writeln!(out, "[default: {}]", escape(&arg.default_values.join(", ")))?;
+    }
+    if !arg.possible_values.is_empty() {
+        paragraph(out)?;
+        writeln!(out, "Possible values:")?;
+        writeln!(out, ".RS")?;
+        for value in &arg.possible_values {
+            writeln!(out, ".IP \\(bu 2")?;
+            let help = text::flatten(&value.description);
+            match help.is_empty() {
+                true => writeln!(out, "\\fB{}\\fR", escape(&value.name))?,
+                false => writeln!(out, "\\fB{}\\fR: {}", escape(&value.name), escape(&help))?,
+            }
+        }
+        writeln!(out, ".RE")?;
+    }
+    if let Some(env) = &arg.env {
+        paragraph(out)?;
+        writeln!(out, "[env: {}]", escape(env))?;
+    }
+    if !arg.value_count.is_empty() {
+        paragraph(out)?;
+        writeln!(out, "{}.", escape(&arg.value_count))?;
+    }
+    if let Some(delimiter) = arg.value_delimiter {
+        paragraph(out)?;
+        writeln!(out, "[values separated by: {}]", escape(&delimiter.to_string()))?;
+    }
+
+    if !arg.conflicts_with.is_empty() {
+        paragraph(out)?;
+        writeln!(out, "[conflicts with: {}]", bold_list(&arg.conflicts_with))?;
+    }
+    if let Some(name) = defined_in {
+        paragraph(out)?;
+        writeln!(out, "[defined in: \\fB{}\\fR(1)]", escape(name))?;
+    }
+
+    Ok(())
+}
+
+/// Format argument names as a comma separated list of bold words.
+fn bold_list(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| format!("\\fB{}\\fR", escape(arg)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Write the paragraphs, lists and code blocks of `text` as roff.
+///
+/// `separator` is the macro starting every paragraph after the first block.
+fn write_paragraphs(out: &mut impl Write, text: &str, separator: &str) -> fmt::Result {
+    for (i, block) in text::blocks(text).iter().enumerate() {
+        match block {
+            Block::Paragraph(text) => {
+                if i > 0 {
+                    writeln!(out, "{}", separator)?;
+                }
+                writeln!(out, "{}", escape(text))?;
+            }
+            Block::List(items) => {
+                writeln!(out, ".RS")?;
+                for item in items {
+                    writeln!(out, ".IP \\(bu 2")?;
+                    writeln!(out, "{}", escape(item))?;
+                }
+                writeln!(out, ".RE")?;
+            }
+            Block::Code(lines) => {
+                writeln!(out, ".RS")?;
+                writeln!(out, ".nf")?;
+                for line in lines {
+                    writeln!(out, "{}", escape(line))?;
+                }
+                writeln!(out, ".fi")?;
+                writeln!(out, ".RE")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Make the command chain and flags of a usage line bold and the placeholders
+/// italic.
+fn fmt_usage(line: &DocUsage) -> String {
+    line.tokens
+        .iter()
+        .map(|t| match t.kind {
+            DocUsageTokenKind::Binary | DocUsageTokenKind::Subcommand | DocUsageTokenKind::Flag => {
+                format!("\\fB{}\\fR", escape(&t.text))
+            }
+            DocUsageTokenKind::Placeholder => format!("\\fI{}\\fR", escape(&t.text)),
+            _ => escape(&t.text),
+        })
+        .collect()
+}
+
+/// Make the flag names bold, leaving the value placeholders as they are.
+fn fmt_flags(flags: &str) -> String {
+    flags
+        .split_whitespace()
+        .map(|token| match (token.starts_with('-'), token.strip_suffix(',')) {
+            (true, Some(flag)) => format!("\\fB{}\\fR,", escape(flag)),
+            (true, None) => format!("\\fB{}\\fR", escape(token)),
+            (false, _) => escape(token),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Format a help heading as a section title, e.g. `Output options` becomes
+/// `"OUTPUT OPTIONS"`.
+fn heading(text: &str) -> String {
+    format!("\"{}\"", escape(&text.to_uppercase()).replace('"', "\\(dq"))
+}
+
+/// First paragraph of a description, used for the `NAME` section.
+fn summary(description: &str) -> Option<String> {
+    text::blocks(description).into_iter().find_map(|block| match block {
+        Block::Paragraph(text) => Some(text),
+        _ => None,
+    })
+}
+
+/// Page name of a command, e.g. `mycli-sub` for `mycli sub`.
+fn page_name(files: &FileNames, cmd_chain: &str) -> String {
+    files.get(cmd_chain).unwrap_or_default().to_string()
+}
+
+/// Escape `text` so roff prints it verbatim.
+fn escape(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    match text.starts_with('.') || text.starts_with('\'') {
+        true => format!("\\&{}", text),
+        false => text,
+    }
+}
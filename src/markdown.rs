@@ -0,0 +1,434 @@
+//! Markdown backend.
+//!
+//! Renders the same [`DocPage`] used by the HTML template as a single Markdown
+//! document, suited for GitHub and mdBook.
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use crate::text::{self, Block, Span};
+use crate::{DocArg, DocCommand, DocPage};
+
+/// Render `page` as a Markdown document.
+pub(crate) fn render(page: &DocPage, out: &mut impl Write) -> fmt::Result {
+    let mut headings = Headings::default();
+    headings.add_command(&page.main.cmd_chain);
+    writeln!(out, "# {}", escape(&page.main.cmd_chain))?;
+    writeln!(out)?;
+    write_header(out, &page.main)?;
+    write_description(out, &page.main.before_help)?;
+    write_description(out, &page.main.description)?;
+
+    // The table of contents links to headings written after it, so the rest
+    // of the document is written first to know their anchors
+    let toc = !page.subcommands.is_empty();
+    if toc {
+        headings.add("Table of contents");
+    }
+    let mut body = String::new();
+    write_usage(&mut body, &mut headings, &page.main)?;
+    write_notes(&mut body, &mut headings, &page.main)?;
+
+    for t in &page.subcommands {
+        headings.add_command(&t.cmd_chain);
+        writeln!(body, "## {}", escape(&t.cmd_chain))?;
+        writeln!(body)?;
+        write_description(&mut body, &t.before_help)?;
+        write_description(&mut body, &t.description)?;
+        write_usage(&mut body, &mut headings, t)?;
+        write_notes(&mut body, &mut headings, t)?;
+    }
+
+    if toc {
+        writeln!(out, "## Table of contents")?;
+        writeln!(out)?;
+        let depth = page.main.cmd_chain.split(' ').count();
+        for t in &page.subcommands {
+            let indent = t.cmd_chain.split(' ').count() - depth - 1;
+            writeln!(
+                out,
+                "{}- [{}](#{})",
+                "  ".repeat(indent),
+                escape(&t.cmd_chain),
+                headings.command(&t.cmd_chain)
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    out.write_str(&body)
+}
+
+/// Anchors of the headings written so far.
+///
+/// GitHub and mdBook add `-1`, `-2`, ... to the anchor of a heading whose
+/// slug is already used, so `mycli a.b` and `mycli ab` link to different
+/// sections.
+#[derive(Default)]
+struct Headings {
+    /// Times each slug was used
+    used: HashMap<String, usize>,
+    /// Anchor of each command, by command chain
+    commands: HashMap<String, String>,
+}
+
+impl Headings {
+    /// Record a heading, returning its anchor.
+    fn add(&mut self, heading: &str) -> String {
+        let base = slug(heading);
+        let mut anchor = base.clone();
+        while self.used.contains_key(&anchor) {
+            let count = self.used.entry(base.clone()).or_default();
+            *count += 1;
+            anchor = format!("{}-{}", base, count);
+        }
+        self.used.insert(anchor.clone(), 0);
+        anchor
+    }
+
+    /// Record the heading of a command.
+    fn add_command(&mut self, cmd_chain: &str) -> String {
+        let anchor = self.add(cmd_chain);
+        self.commands.insert(cmd_chain.to_string(), anchor.clone());
+        anchor
+    }
+
+    /// Anchor of the heading of a command.
+    fn command(&self, cmd_chain: &str) -> String {
+        self.commands.get(cmd_chain).cloned().unwrap_or_else(|| slug(cmd_chain))
+    }
+}
+
+/// Write a section heading, recording its anchor.
+fn heading(out: &mut impl Write, headings: &mut Headings, text: &str) -> fmt::Result {
+    headings.add(text);
+    writeln!(out, "### {}", escape(text))?;
+    writeln!(out)
+}
+
+/// Version and author of the main command. Subcommands usually share them,
+/// so they are only written once.
+fn write_header(out: &mut impl Write, data: &DocCommand) -> fmt::Result {
+    let mut lines = Vec::new();
+    if let Some(version) = &data.version {
+        lines.push(format!("Version: {}", escape(&text::flatten(version))));
+    }
+    if let Some(author) = &data.author {
+        lines.push(format!("Author: {}", escape(&text::flatten(author))));
+    }
+    if !lines.is_empty() {
+        // Two trailing spaces break the line without starting a paragraph
+        writeln!(out, "{}", lines.join("  \n"))?;
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Text shown after the help of a command, such as examples.
+fn write_notes(out: &mut impl Write, headings: &mut Headings, data: &DocCommand) -> fmt::Result {
+    if data.after_help.trim().is_empty() {
+        return Ok(());
+    }
+
+    heading(out, headings, "Notes")?;
+    write_description(out, &data.after_help)
+}
+
+fn write_description(out: &mut impl Write, description: &str) -> fmt::Result {
+    for block in text::blocks(description) {
+        match block {
+            Block::Paragraph(text) => writeln!(out, "{}", inline(&text))?,
+            Block::List(items) => {
+                for item in items {
+                    writeln!(out, "- {}", inline(&item))?;
+                }
+            }
+            Block::Code(lines) => {
+                let fence = fence(&lines);
+                writeln!(out, "{}text", fence)?;
+                for line in lines {
+                    writeln!(out, "{}", line)?;
+                }
+                writeln!(out, "{}", fence)?;
+            }
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Lines of a help text for a table cell, where blocks can't be nested.
+///
+/// Blocks are separated by an empty line.
+fn help_lines(description: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for block in text::blocks(description) {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        match block {
+            Block::Paragraph(text) => lines.push(inline(&text)),
+            Block::List(items) => {
+                lines.extend(items.iter().map(|item| format!("- {}", inline(item))));
+            }
+            Block::Code(code) => {
+                lines.extend(code.iter().filter(|l| !l.is_empty()).map(|l| self::code(l)));
+            }
+        }
+    }
+
+    lines
+}
+
+/// Text of a paragraph or list item, with its URLs turned into autolinks.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    for span in text::spans(text) {
+        match span {
+            Span::Text(text) if out.is_empty() => out.push_str(&escape(text)),
+            // Only the start of the line can be taken for a heading or a list
+            Span::Text(text) => out.push_str(&escape_inline(text)),
+            Span::Link(url) => out.push_str(&format!("<{}>", url)),
+        }
+    }
+    out
+}
+
+/// Markdown counterpart of `usage-partial.html`.
+fn write_usage(out: &mut impl Write, headings: &mut Headings, data: &DocCommand) -> fmt::Result {
+    if !data.flags.is_empty() {
+        writeln!(out, "Flags: {}", code(&data.flags))?;
+        writeln!(out)?;
+    }
+    if !data.aliases.is_empty() {
+        writeln!(out, "Aliases: {}", code_list(&data.aliases))?;
+        writeln!(out)?;
+    }
+
+    let lines = data.usages.iter().map(|t| t.text.clone()).collect::<Vec<String>>();
+    let fence = fence(&lines);
+    writeln!(out, "{}text", fence)?;
+    for line in &lines {
+        writeln!(out, "{}", line)?;
+    }
+    writeln!(out, "{}", fence)?;
+    writeln!(out)?;
+
+    if !data.commands.is_empty() {
+        heading(out, headings, &data.commands_heading)?;
+        writeln!(out, "| Command | Description |")?;
+        writeln!(out, "| --- | --- |")?;
+        for t in &data.commands {
+            let name = match t.flags.is_empty() {
+                true => t.name.clone(),
+                false => format!("{}, {}", t.name, t.flags),
+            };
+            let mut lines = help_lines(&t.description);
+            if !t.aliases.is_empty() {
+                lines.push(format!("Aliases: {}", code_list(&t.aliases)));
+            }
+            writeln!(out, "| {} | {} |", cell(&code(&name)), cell(&lines.join("\n")))?;
+        }
+        writeln!(out)?;
+    }
+
+    write_args(out, headings, "Arguments", "Argument", &data.arguments)?;
+    write_args(out, headings, "Options", "Option", &data.options)?;
+    for t in &data.sections {
+        write_args(out, headings, &t.heading, "Argument", &t.args)?;
+    }
+
+    if !data.inherited.is_empty() {
+        heading(out, headings, "Inherited options")?;
+        writeln!(out, "| Option | Description |")?;
+        writeln!(out, "| --- | --- |")?;
+        for t in &data.inherited {
+            let defined_in = format!(
+                "Defined in: [{}](#{})",
+                escape(&t.cmd_chain),
+                headings.command(&t.cmd_chain)
+            );
+            let description = arg_description(&t.arg, &[defined_in]);
+            writeln!(out, "| {} | {} |", cell(&code(&t.arg.flags)), cell(&description))?;
+        }
+        writeln!(out)?;
+    }
+
+    if !data.groups.is_empty() {
+        heading(out, headings, "Groups")?;
+        writeln!(out, "| Group | Description |")?;
+        writeln!(out, "| --- | --- |")?;
+        for t in &data.groups {
+            let rule = format!("{}: {}", escape(&t.rule), code_list(&t.args));
+            writeln!(out, "| {} | {} |", cell(&code(&t.name)), cell(&rule))?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+fn write_args(
+    out: &mut impl Write,
+    headings: &mut Headings,
+    title: &str,
+    column: &str,
+    args: &[DocArg],
+) -> fmt::Result {
+    if args.is_empty() {
+        return Ok(());
+    }
+
+    heading(out, headings, title)?;
+    writeln!(out, "| {} | Description |", column)?;
+    writeln!(out, "| --- | --- |")?;
+    for t in args {
+        writeln!(out, "| {} | {} |", cell(&code(&t.flags)), cell(&arg_description(t, &[])))?;
+    }
+    writeln!(out)
+}
+
+/// Description of an argument followed by its details and the `extra` lines.
+fn arg_description(arg: &DocArg, extra: &[String]) -> String {
+    let mut lines = help_lines(&arg.description);
+    lines.extend(arg_details(arg));
+    lines.extend_from_slice(extra);
+
+    lines.join("\n")
+}
+
+/// Lines describing the aliases, default values, possible values and
+/// environment variable of an argument.
+fn arg_details(arg: &DocArg) -> Vec<String> {
+    let mut lines = Vec::new();
+    if !arg.aliases.is_empty() {
+        lines.push(format!("Aliases: {}", code_list(&arg.aliases)));
+    }
+    if !arg.default_values.is_empty() {
+        lines.push(format!("Default: {}", code_list(&arg.default_values)));
+    }
+    if !arg.possible_values.is_empty() {
+        lines.push("Possible values:".to_string());
+        for value in &arg.possible_values {
+            match summary(&value.description) {
+                Some(help) => lines.push(format!("- {}: {}", code(&value.name), help)),
+                None => lines.push(format!("- {}", code(&value.name))),
+            }
+        }
+    }
+    if let Some(env) = &arg.env {
+        lines.push(format!("Environment: {}", code(env)));
+    }
+    if !arg.value_count.is_empty() {
+        lines.push(escape(&arg.value_count));
+    }
+    if let Some(delimiter) = arg.value_delimiter {
+        lines.push(format!("Values separated by {}", code(&delimiter.to_string())));
+    }
+    if !arg.conflicts_with.is_empty() {
+        lines.push(format!("Conflicts with: {}", code_list(&arg.conflicts_with)));
+    }
+
+    lines
+}
+
+/// Format `values` as a comma separated list of inline code.
+fn code_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| code(v))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Format `value` as inline code, delimited by more backticks than it contains.
+fn code(value: &str) -> String {
+    let ticks = "`".repeat(longest_backtick_run(value) + 1);
+    // A space keeps backticks at the edges apart from the delimiters, and is
+    // stripped by the renderers
+    match value.starts_with('`') || value.ends_with('`') || value.trim().is_empty() {
+        true => format!("{} {} {}", ticks, value, ticks),
+        false => format!("{}{}{}", ticks, value, ticks),
+    }
+}
+
+/// Fence of a code block holding `lines`, longer than any backtick run in them.
+fn fence(lines: &[String]) -> String {
+    let longest = lines.iter().map(|l| longest_backtick_run(l)).max().unwrap_or(0);
+    "`".repeat((longest + 1).max(3))
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+/// Help of a possible value on a single line.
+fn summary(description: &str) -> Option<String> {
+    let summary = text::flatten(description);
+    match summary.is_empty() {
+        true => None,
+        false => Some(escape(&summary)),
+    }
+}
+
+/// Escape the characters of `text` that Markdown or HTML would interpret,
+/// including the markers of a heading or list at the start of a line.
+fn escape(text: &str) -> String {
+    let escaped = escape_inline(text);
+    let digits = escaped.len() - escaped.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let hashes = escaped.len() - escaped.trim_start_matches('#').len();
+    // Markers are followed by a space, or end the line
+    let ends_marker = |i: usize| escaped[i..].chars().next().is_none_or(char::is_whitespace);
+    let marker = match escaped[digits..].chars().next() {
+        Some('.' | ')') if digits > 0 && ends_marker(digits + 1) => Some(digits),
+        Some('#') if ends_marker(hashes) => Some(0),
+        // `---` is a thematic break
+        Some('-') if ends_marker(1) || escaped.starts_with("---") => Some(0),
+        Some('+') if ends_marker(1) => Some(0),
+        _ => None,
+    };
+
+    match marker {
+        Some(i) => format!("{}\\{}", &escaped[..i], &escaped[i..]),
+        None => escaped,
+    }
+}
+
+/// Escape the characters of `text` that Markdown or HTML would interpret
+/// anywhere in a line.
+///
+/// `|` is left to [`cell`], since it only matters in tables.
+fn escape_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '&' | '~') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escape `text` so it fits in a single table cell.
+fn cell(text: &str) -> String {
+    text.trim()
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Build the anchor GitHub and mdBook generate for a heading.
+fn slug(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+//! Documentation model extracted from a [`clap::Command`].
+//!
+//! Every backend renders this model, and it is also what the templates get as
+//! their data.
+
+use serde_derive::Serialize;
+
+/// Documentation of a command and all of its subcommands.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocPage {
+    /// The command the documentation was generated for.
+    pub main: DocCommand,
+    /// Every subcommand of `main`, recursively, in depth-first order.
+    pub subcommands: Vec<DocCommand>,
+    /// The command tree, rooted at `main`, in the same order as `subcommands`.
+    pub tree: DocTreeNode,
+}
+
+/// A command of the command tree, with its subcommands.
+///
+/// ```
+/// use clap::Command;
+///
+/// let command = Command::new("mycli")
+///     .subcommand(Command::new("cloud").subcommand(Command::new("deploy")))
+///     .subcommand(Command::new("version"));
+/// let page = clap_show::extract(&command);
+///
+/// let cloud = &page.tree.children[0];
+/// assert_eq!(cloud.cmd_chain, "mycli cloud");
+/// assert_eq!(cloud.children[0].name, "deploy");
+/// assert_eq!(page.tree.children[1].name, "version");
+/// ```
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocTreeNode {
+    /// Name of the command.
+    pub name: String,
+    /// Names of the command and all its parents, e.g. `mycli sub`.
+    pub cmd_chain: String,
+    /// Anchor of the command in the single HTML page.
+    pub anchor: String,
+    /// Direct subcommands.
+    pub children: Vec<DocTreeNode>,
+}
+
+/// Documentation of a single command.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocCommand {
+    /// Name of the command.
+    pub title: String,
+    /// Flags the command is called with, like pacman's `-S, --sync`, empty
+    /// unless set with [`Command::short_flag`](clap::Command::short_flag) or
+    /// [`Command::long_flag`](clap::Command::long_flag).
+    pub flags: String,
+    /// Other names of the command, as typed on the command line, e.g. `-s`,
+    /// `--synchronize` or `sy`.
+    pub aliases: Vec<String>,
+    /// First usage line of the command, without the command chain, e.g.
+    /// `[OPTIONS] <FILE>`.
+    pub usage: String,
+    /// Every usage line of the command, split into tokens.
+    pub usages: Vec<DocUsage>,
+    /// Names of the command and all its parents, e.g. `mycli sub`.
+    pub cmd_chain: String,
+    /// Anchor of the command in the single HTML page, e.g. `mycli-sub`, unique
+    /// across the page.
+    pub anchor: String,
+    /// Help of the command, long or short depending on the
+    /// [`Verbosity`](crate::Verbosity), falling back to the other one.
+    pub description: String,
+    /// Version of the command, long or short depending on the
+    /// [`Verbosity`](crate::Verbosity). Subcommands only have one when it is
+    /// set on them or propagated with
+    /// [`Command::propagate_version`](clap::Command::propagate_version).
+    pub version: Option<String>,
+    /// Author of the command, set with [`Command::author`](clap::Command::author).
+    pub author: Option<String>,
+    /// Text shown before the help, set with
+    /// [`Command::before_help`](clap::Command::before_help) or
+    /// [`Command::before_long_help`](clap::Command::before_long_help).
+    pub before_help: String,
+    /// Text shown after the help, often examples or notes, set with
+    /// [`Command::after_help`](clap::Command::after_help) or
+    /// [`Command::after_long_help`](clap::Command::after_long_help).
+    pub after_help: String,
+    /// Heading of the subcommands listing, `Commands` unless set with
+    /// [`Command::subcommand_help_heading`](clap::Command::subcommand_help_heading).
+    pub commands_heading: String,
+    /// Direct subcommands.
+    pub commands: Vec<DocSubcommand>,
+    /// Positional arguments without a help heading.
+    pub arguments: Vec<DocArg>,
+    /// Flags and options without a help heading.
+    pub options: Vec<DocArg>,
+    /// Arguments with a help heading, grouped by heading in the order the
+    /// headings first appear.
+    pub sections: Vec<DocSection>,
+    /// Global arguments inherited from a parent command.
+    pub inherited: Vec<DocInheritedArg>,
+    /// Argument groups that restrict how their arguments are used together.
+    pub groups: Vec<DocGroup>,
+}
+
+/// A usage line, as printed by `--help`.
+///
+/// ```
+/// use clap::{Arg, Command};
+/// use clap_show::DocUsageTokenKind;
+///
+/// let command = Command::new("mycli").arg(Arg::new("file").long("file").required(true));
+/// let page = clap_show::extract(&command);
+/// let usage = &page.main.usages[0];
+///
+/// assert_eq!(usage.text, "mycli --file <file>");
+/// let placeholders: Vec<&str> = usage
+///     .tokens
+///     .iter()
+///     .filter(|t| t.kind == DocUsageTokenKind::Placeholder)
+///     .map(|t| t.text.as_str())
+///     .collect();
+/// assert_eq!(placeholders, ["file"]);
+/// ```
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocUsage {
+    /// The whole line, e.g. `mycli sub [OPTIONS] <FILE>`.
+    pub text: String,
+    /// The line split into tokens. Joining their texts gives back `text`.
+    pub tokens: Vec<DocUsageToken>,
+}
+
+/// A part of a usage line.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocUsageToken {
+    /// What the token is.
+    pub kind: DocUsageTokenKind,
+    /// Text of the token, e.g. `--file`, `FILE` or `...`.
+    pub text: String,
+}
+
+/// Kind of a [`DocUsageToken`].
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DocUsageTokenKind {
+    /// Name of the binary, e.g. `mycli` or `cargo mycli`.
+    Binary,
+    /// Name of a subcommand leading to the command.
+    Subcommand,
+    /// A flag, e.g. `--file` or `-f`.
+    Flag,
+    /// Name of a value, without its brackets, e.g. `FILE` in `<FILE>`, or
+    /// `OPTIONS` in `[OPTIONS]`.
+    Placeholder,
+    /// A bracket opening or closing an optional part, `[` or `]`.
+    Optional,
+    /// The `...` marking a part that can be repeated.
+    Repetition,
+    /// Anything else: spaces, `<`, `>`, `{`, `}`, `|`, `=` and the `--`
+    /// separator.
+    Text,
+}
+
+/// Entry of a subcommand in the listing of its parent.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocSubcommand {
+    /// Name of the subcommand.
+    pub name: String,
+    /// Flags the subcommand is called with, e.g. `-S, --sync`, or empty.
+    pub flags: String,
+    /// Other names of the subcommand, e.g. `-s`, `--synchronize` or `sy`.
+    pub aliases: Vec<String>,
+    /// Short help of the subcommand, or the long help when there is no short one.
+    pub description: String,
+}
+
+/// Documentation of a positional argument, flag or option.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocArg {
+    /// Signature of the argument, e.g. `-f, --file <FILE>`.
+    pub flags: String,
+    /// Other names of the argument, e.g. `-i` or `--input`.
+    pub aliases: Vec<String>,
+    /// Anchor of the argument in the single HTML page, e.g.
+    /// `mycli-sub--force`, unique across the page.
+    pub anchor: String,
+    /// Help of the argument, long or short depending on the
+    /// [`Verbosity`](crate::Verbosity), falling back to the other one.
+    pub description: String,
+    /// Values used when the argument is not given, unless hidden with
+    /// [`Arg::hide_default_value`](clap::Arg::hide_default_value).
+    pub default_values: Vec<String>,
+    /// Values the argument accepts, unless hidden with
+    /// [`Arg::hide_possible_values`](clap::Arg::hide_possible_values).
+    pub possible_values: Vec<DocPossibleValue>,
+    /// Environment variable the argument is read from, unless hidden with
+    /// [`Arg::hide_env`](clap::Arg::hide_env).
+    pub env: Option<String>,
+    /// Character separating several values given at once, e.g. `,` for
+    /// `--file a,b`.
+    pub value_delimiter: Option<char>,
+    /// Fewest values given each time the argument is used, e.g. `0` for a
+    /// flag or for `--color[=<WHEN>]`.
+    pub min_values: usize,
+    /// Most values given each time the argument is used, `None` when there's
+    /// no limit, e.g. for `<FILE>...`.
+    pub max_values: Option<usize>,
+    /// How many values the argument takes, derived from `min_values` and
+    /// `max_values`, e.g. `Takes 2 to 3 values`. Empty for a flag or an
+    /// argument taking a single value.
+    pub value_count: String,
+    /// Arguments that can't be used along with this one, e.g. `--json`.
+    ///
+    /// clap doesn't expose the arguments one requires, so `requires` and
+    /// `required_unless_present` aren't documented.
+    pub conflicts_with: Vec<String>,
+}
+
+/// Arguments listed under a custom
+/// [`Arg::help_heading`](clap::Arg::help_heading).
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocSection {
+    /// The help heading.
+    pub heading: String,
+    /// Arguments with this heading, positional or not.
+    pub args: Vec<DocArg>,
+}
+
+/// A global argument a command inherits from one of its parents.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocInheritedArg {
+    /// The argument.
+    pub arg: DocArg,
+    /// Command chain of the command defining the argument, e.g. `mycli`.
+    pub cmd_chain: String,
+    /// Anchor of the command defining the argument in the single HTML page.
+    pub anchor: String,
+}
+
+/// An [`ArgGroup`](clap::ArgGroup) of a command.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocGroup {
+    /// Name of the group.
+    pub name: String,
+    /// Arguments of the group, e.g. `--file` or `<PATH>`.
+    pub args: Vec<String>,
+    /// Whether one of the arguments must be used.
+    pub required: bool,
+    /// Whether more than one of the arguments can be used at once.
+    pub multiple: bool,
+    /// How the arguments can be used together, derived from `required` and
+    /// `multiple`, e.g. `Exactly one of these is required`.
+    pub rule: String,
+}
+
+/// A value accepted by an argument.
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct DocPossibleValue {
+    /// The value, as typed on the command line.
+    pub name: String,
+    /// Help of the value, empty when it has none.
+    pub description: String,
+}
@@ -0,0 +1,314 @@
+//! Ramhorns (Mustache) implementation of [`Renderer`].
+
+use std::collections::BTreeMap;
+
+use ramhorns::encoding::Encoder;
+use ramhorns::traits::ContentSequence;
+use ramhorns::{Content, Section, Template};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{html, page, site, theme, DocPage, Error, Renderer, Result, SitePage};
+
+static TEMPLATE_FILE: &str = include_str!("../data/mustache/template.html");
+static CODE_PARTIAL: &str = include_str!("../data/mustache/usage-partial.html");
+static ARG_PARTIAL: &str = include_str!("../data/mustache/arg-details.html");
+static SEARCH_PARTIAL: &str = include_str!("../data/search.html");
+static NAV_PARTIAL: &str = include_str!("../data/mustache/nav.html");
+static SITE_PAGE_FILE: &str = include_str!("../data/mustache/site-page.html");
+static SITE_INDEX_FILE: &str = include_str!("../data/mustache/site-index.html");
+
+/// Bundled templates and partials, by name.
+static DEFAULT_TEMPLATES: [(&str, &str); 8] = [
+    ("template", TEMPLATE_FILE),
+    ("usage-partial", CODE_PARTIAL),
+    ("arg-details", ARG_PARTIAL),
+    ("style", theme::DEFAULT_STYLE),
+    ("search", SEARCH_PARTIAL),
+    ("nav", NAV_PARTIAL),
+    ("site-page", SITE_PAGE_FILE),
+    ("site-index", SITE_INDEX_FILE),
+];
+
+/// How deep partials can be nested before giving up, to catch recursive partials.
+const MAX_PARTIAL_DEPTH: usize = 32;
+
+/// Render the documentation with [Mustache](https://mustache.github.io/)
+/// templates, using [ramhorns](https://docs.rs/ramhorns).
+///
+/// Templates get the same data as the handlebars ones. Mustache has no
+/// helpers, so every field `foo` also comes with two virtual fields:
+///
+/// - `has_foo`: whether `foo` is set and not empty, e.g. `{{#has_options}}`.
+/// - `foo_html`: the text of `foo`, escaped and formatted as paragraphs, lists,
+///   code blocks and links, like the `paragraph` handlebars helper, e.g.
+///   `{{{description_html}}}`.
+///
+/// Lists of strings are iterated with `{{.}}`.
+#[derive(Debug, Clone)]
+pub struct RamhornsRenderer {
+    sources: BTreeMap<String, String>,
+    trusted_html: bool,
+}
+
+impl RamhornsRenderer {
+    /// Create a renderer with the bundled templates and partials.
+    pub fn new() -> Result<Self> {
+        let mut renderer = RamhornsRenderer {
+            sources: BTreeMap::new(),
+            trusted_html: false,
+        };
+        for (name, source) in DEFAULT_TEMPLATES {
+            renderer.register(name, source)?;
+        }
+
+        Ok(renderer)
+    }
+
+    /// Write the `_html` virtual fields without escaping the help texts.
+    /// Defaults to `false`.
+    ///
+    /// Only enable this when every help text of the command is trusted HTML.
+    pub fn trusted_html(&mut self, trusted: bool) {
+        self.trusted_html = trusted;
+    }
+
+    /// Register `source` as the template or partial called `name`, replacing
+    /// the bundled one if any.
+    ///
+    /// Partials are resolved when rendering, so they can be registered in any
+    /// order. The source is still compiled on its own, so syntax errors are
+    /// reported with the name of the partial they are in.
+    pub fn register(&mut self, name: &str, source: &str) -> Result<()> {
+        // ramhorns accepts sections left open at the end of a template, which
+        // fail once inlined into another one, so the source is wrapped in one
+        let wrapped = format!("{{{{#partial}}}}{}{{{{/partial}}}}", without_partials(source));
+        Template::new(wrapped).map_err(|err| Error::Template {
+            name: name.to_string(),
+            source: Box::new(err),
+        })?;
+
+        self.sources.insert(name.to_string(), source.to_string());
+        Ok(())
+    }
+
+    /// Compile the template called `name`, with all its partials inlined.
+    fn template(&self, name: &str) -> Result<Template<'static>> {
+        let source = self.inline_partials(name, 0)?;
+        Template::new(source).map_err(|err| Error::Template {
+            name: name.to_string(),
+            source: Box::new(err),
+        })
+    }
+
+    /// Replace every `{{> partial}}` tag in the template called `name` with the
+    /// source of the partial.
+    fn inline_partials(&self, name: &str, depth: usize) -> Result<String> {
+        let missing = |reason: String| Error::Template {
+            name: name.to_string(),
+            source: reason.into(),
+        };
+
+        if depth > MAX_PARTIAL_DEPTH {
+            return Err(missing(format!("partials nested deeper than {}", MAX_PARTIAL_DEPTH)));
+        }
+        let source = self
+            .sources
+            .get(name)
+            .ok_or_else(|| missing(format!("template `{}` not found", name)))?;
+
+        let mut out = String::with_capacity(source.len());
+        let mut rest = source.as_str();
+        while let Some(start) = rest.find("{{>") {
+            let end = rest[start..]
+                .find("}}")
+                .ok_or_else(|| missing("unclosed partial tag".to_string()))?;
+            let partial = rest[start + 3..start + end].trim();
+
+            out.push_str(&rest[..start]);
+            out.push_str(&self.inline_partials(partial, depth + 1)?);
+            rest = &rest[start + end + 2..];
+        }
+        out.push_str(rest);
+
+        Ok(out)
+    }
+}
+
+/// `source` without its `{{> partial}}` tags, which ramhorns can't compile
+/// without loading the partials.
+fn without_partials(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut rest = source;
+    while let Some(start) = rest.find("{{>") {
+        let end = match rest[start..].find("}}") {
+            Some(end) => end,
+            None => break,
+        };
+        out.push_str(&rest[..start]);
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+impl Renderer for RamhornsRenderer {
+    fn render_page(&self, page: &DocPage) -> Result<String> {
+        let template = self.template("template")?;
+        render(&template, &page::single_page(page)?, self.trusted_html)
+    }
+
+    fn render_site(&self, page: &DocPage) -> Result<Vec<SitePage>> {
+        let index = self.template("site-index")?;
+        let command = self.template("site-page")?;
+
+        site::render(
+            page,
+            |data| render(&index, data, self.trusted_html),
+            |data| render(&command, data, self.trusted_html),
+        )
+    }
+}
+
+fn render<T: Serialize>(template: &Template, data: &T, trusted: bool) -> Result<String> {
+    let value = serde_json::to_value(data)?;
+    Ok(template.render(&Json { value: &value, trusted }))
+}
+
+/// Render any serialized value, following the mustache rules: `null`, `false`,
+/// empty strings and empty lists are falsy, lists are iterated and objects are
+/// pushed on the context stack.
+///
+/// `trusted` tells whether the `_html` virtual fields are written unescaped.
+#[derive(Clone, Copy)]
+struct Json<'a> {
+    value: &'a Value,
+    trusted: bool,
+}
+
+/// A field of an object, real or virtual.
+enum Field<'a> {
+    Value(&'a Value),
+    Flag(bool),
+    Html(String),
+}
+
+impl<'a> Json<'a> {
+    fn child(&self, value: &'a Value) -> Json<'a> {
+        Json {
+            value,
+            trusted: self.trusted,
+        }
+    }
+
+    fn get(&self, path: &str) -> Option<&'a Value> {
+        if path == "." {
+            return Some(self.value);
+        }
+        path.split('.').try_fold(self.value, |value, name| value.get(name))
+    }
+
+    fn field(&self, name: &str) -> Option<Field<'a>> {
+        if let Some(value) = self.get(name) {
+            return Some(Field::Value(value));
+        }
+        if let Some(value) = name.strip_prefix("has_").and_then(|n| self.get(n)) {
+            return Some(Field::Flag(self.child(value).is_truthy()));
+        }
+        let value = self.get(name.strip_suffix("_html")?)?;
+        Some(Field::Html(html::help_text(value.as_str().unwrap_or_default(), self.trusted)))
+    }
+}
+
+impl Content for Json<'_> {
+    fn is_truthy(&self) -> bool {
+        match self.value {
+            Value::Null => false,
+            Value::Bool(value) => *value,
+            Value::String(value) => !value.is_empty(),
+            Value::Array(values) => !values.is_empty(),
+            Value::Number(_) | Value::Object(_) => true,
+        }
+    }
+
+    fn render_escaped<E: Encoder>(&self, encoder: &mut E) -> std::result::Result<(), E::Error> {
+        match self.value {
+            Value::String(value) => encoder.write_escaped(value),
+            Value::Number(_) | Value::Bool(_) => encoder.write_escaped(&self.value.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn render_unescaped<E: Encoder>(&self, encoder: &mut E) -> std::result::Result<(), E::Error> {
+        match self.value {
+            Value::String(value) => encoder.write_unescaped(value),
+            Value::Number(_) | Value::Bool(_) => encoder.write_unescaped(&self.value.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn render_section<C, E>(&self, section: Section<C>, encoder: &mut E) -> std::result::Result<(), E::Error>
+    where
+        C: ContentSequence,
+        E: Encoder,
+    {
+        match self.value {
+            Value::Array(values) => {
+                for value in values {
+                    self.child(value).render_section(section, encoder)?;
+                }
+                Ok(())
+            }
+            _ if self.is_truthy() => section.with(self).render(encoder),
+            _ => Ok(()),
+        }
+    }
+
+    fn render_field_escaped<E: Encoder>(&self, _: u64, name: &str, encoder: &mut E) -> std::result::Result<bool, E::Error> {
+        match self.field(name) {
+            Some(Field::Value(value)) => self.child(value).render_escaped(encoder)?,
+            Some(Field::Flag(flag)) => flag.render_escaped(encoder)?,
+            Some(Field::Html(html)) => encoder.write_escaped(&html)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn render_field_unescaped<E: Encoder>(&self, _: u64, name: &str, encoder: &mut E) -> std::result::Result<bool, E::Error> {
+        match self.field(name) {
+            Some(Field::Value(value)) => self.child(value).render_unescaped(encoder)?,
+            Some(Field::Flag(flag)) => flag.render_unescaped(encoder)?,
+            Some(Field::Html(html)) => encoder.write_unescaped(&html)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn render_field_section<C, E>(&self, _: u64, name: &str, section: Section<C>, encoder: &mut E) -> std::result::Result<bool, E::Error>
+    where
+        C: ContentSequence,
+        E: Encoder,
+    {
+        match self.field(name) {
+            Some(Field::Value(value)) => self.child(value).render_section(section, encoder)?,
+            Some(Field::Flag(flag)) => flag.render_section(section, encoder)?,
+            Some(Field::Html(html)) => html.render_section(section, encoder)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn render_field_inverse<C, E>(&self, _: u64, name: &str, section: Section<C>, encoder: &mut E) -> std::result::Result<bool, E::Error>
+    where
+        C: ContentSequence,
+        E: Encoder,
+    {
+        match self.field(name) {
+            Some(Field::Value(value)) => self.child(value).render_inverse(section, encoder)?,
+            Some(Field::Flag(flag)) => flag.render_inverse(section, encoder)?,
+            Some(Field::Html(html)) => html.render_inverse(section, encoder)?,
+            None => return Ok(false),
+        }
+        Ok(true)
+    }
+}
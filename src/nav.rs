@@ -0,0 +1,73 @@
+//! Navigation tree of the HTML pages.
+//!
+//! Templates can't recurse in every engine, so the tree is flattened into
+//! items that tell where the nested lists open and close:
+//!
+//! ```text
+//! {{#each nav as |n|}}
+//! <li>
+//!   <a href="{{n.href}}">{{n.name}}</a>{{#if n.has_children}}<ul>{{else}}</li>{{/if}}
+//!   {{#each n.closes}}</ul></li>{{/each}}
+//! {{/each}}
+//! ```
+
+use serde_derive::Serialize;
+
+use crate::DocTreeNode;
+
+/// A command of the navigation tree.
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct NavItem {
+    name: String,
+    cmd_chain: String,
+    anchor: String,
+    href: String,
+    /// Depth of the command below the root of the tree, starting at 1.
+    depth: usize,
+    /// Whether a nested list of subcommands follows the item.
+    has_children: bool,
+    /// Whether the nested list is unfolded when the page loads.
+    open: bool,
+    /// One entry for every nested list the item is the last one of, so the
+    /// template can close them. The entries are the depths of the lists.
+    closes: Vec<usize>,
+}
+
+/// Flatten the trees of `nodes`, in depth-first order.
+///
+/// `href` gives the link to a command. The nested lists down to `open_depth`
+/// are unfolded.
+pub(crate) fn items<F>(nodes: &[DocTreeNode], open_depth: usize, href: F) -> Vec<NavItem>
+where
+    F: Fn(&DocTreeNode) -> String,
+{
+    let mut items = Vec::new();
+    push_items(&mut items, nodes, 1, open_depth, &href);
+    items
+}
+
+fn push_items<F>(items: &mut Vec<NavItem>, nodes: &[DocTreeNode], depth: usize, open_depth: usize, href: &F)
+where
+    F: Fn(&DocTreeNode) -> String,
+{
+    for node in nodes {
+        items.push(NavItem {
+            name: node.name.clone(),
+            cmd_chain: node.cmd_chain.clone(),
+            anchor: node.anchor.clone(),
+            href: href(node),
+            depth,
+            has_children: !node.children.is_empty(),
+            open: depth <= open_depth,
+            closes: Vec::new(),
+        });
+
+        if !node.children.is_empty() {
+            push_items(items, &node.children, depth + 1, open_depth, href);
+            // The last item below this one closes its list
+            if let Some(last) = items.last_mut() {
+                last.closes.push(depth + 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Data of the single page `template`, and of the commands in every HTML
+//! template.
+
+use serde_derive::Serialize;
+
+use crate::{nav, search, DocCommand, DocPage, DocTreeNode, Result};
+
+/// Data of the `template` template: the page, with its search index and
+/// navigation tree.
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct SinglePage<'a> {
+    main: Headed<&'a DocCommand>,
+    subcommands: Vec<Headed<&'a DocCommand>>,
+    tree: &'a DocTreeNode,
+    /// The search index, as JSON that can be embedded in a `<script>` element.
+    search_index: String,
+    nav: Vec<nav::NavItem>,
+}
+
+/// A command, with the level of the headings of its lists, e.g. `2` for
+/// `<h2>` right under the `<h1>` of the page.
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct Headed<T> {
+    #[serde(flatten)]
+    pub(crate) command: T,
+    heading_level: u8,
+}
+
+impl<T> Headed<T> {
+    pub(crate) fn new(command: T, heading_level: u8) -> Self {
+        Headed {
+            command,
+            heading_level,
+        }
+    }
+}
+
+pub(crate) fn single_page(page: &DocPage) -> Result<SinglePage<'_>> {
+    Ok(SinglePage {
+        // The main command is under the `<h1>`, the subcommands under an `<h2>`
+        main: Headed::new(&page.main, 2),
+        subcommands: page.subcommands.iter().map(|t| Headed::new(t, 3)).collect(),
+        tree: &page.tree,
+        search_index: search::single_page(page)?,
+        // The menu unfolds the section in view by itself
+        nav: nav::items(&page.tree.children, 0, |t| format!("#{}", t.anchor)),
+    })
+}
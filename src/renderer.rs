@@ -0,0 +1,47 @@
+use crate::{DocPage, Result, SitePage};
+
+/// Template engine used to turn the documentation model into HTML.
+///
+/// Implement this trait to render the documentation with an engine that is not
+/// bundled with the crate, and plug it with [`ClapShow::renderer`]. The model
+/// implements [`serde::Serialize`], so it can be fed to any serde based engine.
+///
+/// [`ClapShow::renderer`]: crate::ClapShow::renderer
+pub trait Renderer {
+    /// Render the single HTML page documenting every command in `page`.
+    fn render_page(&self, page: &DocPage) -> Result<String>;
+
+    /// Render the static site for `page`: the index and one page per command.
+    fn render_site(&self, page: &DocPage) -> Result<Vec<SitePage>>;
+}
+
+/// Template engines bundled with the crate.
+///
+/// Each engine is behind a cargo feature of the same name, so the one that is
+/// not used can be dropped from the dependency tree. `handlebars` is enabled by
+/// default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Engine {
+    /// [Handlebars](https://docs.rs/handlebars) templates.
+    #[cfg(feature = "handlebars")]
+    Handlebars,
+    /// [Mustache](https://mustache.github.io/) templates, rendered with
+    /// [ramhorns](https://docs.rs/ramhorns).
+    #[cfg(feature = "ramhorns")]
+    Ramhorns,
+}
+
+impl Engine {
+    /// The engine used when none is selected, if any is enabled.
+    pub(crate) fn default_engine() -> Option<Engine> {
+        #[cfg(feature = "handlebars")]
+        return Some(Engine::Handlebars);
+
+        #[cfg(all(not(feature = "handlebars"), feature = "ramhorns"))]
+        return Some(Engine::Ramhorns);
+
+        #[cfg(not(any(feature = "handlebars", feature = "ramhorns")))]
+        return None;
+    }
+}
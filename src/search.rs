@@ -0,0 +1,120 @@
+//! Search index of the HTML pages.
+//!
+//! The index lists every command and argument with the words they can be
+//! found by. It is embedded in the single page, or written beside the pages of
+//! the static site, and searched in the browser by the `search` partial, so
+//! searching works offline.
+
+use serde_derive::Serialize;
+
+use crate::{text, DocArg, DocCommand, DocPage, Result};
+
+/// File of the static site defining the search index, as `window.clapShowSearch`.
+pub(crate) const SITE_FILE: &str = "search-index.js";
+
+/// A command or an argument users can search for.
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct Entry {
+    /// Command chain or flags of the argument, e.g. `--file <FILE>`.
+    title: String,
+    /// Command chain of the command documenting an argument, or of the parent
+    /// of a command.
+    context: String,
+    /// Link to the entry.
+    href: String,
+    /// Lowercase words the entry is found by.
+    keywords: String,
+}
+
+/// Search index of the single page, as JSON that can be embedded in a
+/// `<script>` element.
+pub(crate) fn single_page(page: &DocPage) -> Result<String> {
+    let entries = index(page, |_| String::new());
+    Ok(to_json(&entries)?)
+}
+
+/// Script of the static site defining the search index.
+///
+/// `page_href` gives the file of the page documenting a command.
+pub(crate) fn site_script<F>(page: &DocPage, page_href: F) -> Result<String>
+where
+    F: Fn(&DocCommand) -> String,
+{
+    let entries = index(page, page_href);
+    Ok(format!("window.clapShowSearch = {};\n", to_json(&entries)?))
+}
+
+/// Index every command of `page` and their arguments, inherited ones aside
+/// since they are indexed with the command defining them.
+fn index<F>(page: &DocPage, page_href: F) -> Vec<Entry>
+where
+    F: Fn(&DocCommand) -> String,
+{
+    let mut entries = Vec::new();
+    for command in std::iter::once(&page.main).chain(page.subcommands.iter()) {
+        let href = page_href(command);
+
+        let context = match command.cmd_chain.rsplit_once(' ') {
+            Some((parent, _)) => parent.to_string(),
+            None => String::new(),
+        };
+        entries.push(Entry {
+            title: command.cmd_chain.clone(),
+            context,
+            href: format!("{}#{}", href, command.anchor),
+            keywords: command_keywords(command),
+        });
+
+        let args = command
+            .arguments
+            .iter()
+            .chain(command.options.iter())
+            .chain(command.sections.iter().flat_map(|t| t.args.iter()));
+        for arg in args {
+            entries.push(Entry {
+                title: arg.flags.clone(),
+                context: command.cmd_chain.clone(),
+                href: format!("{}#{}", href, arg.anchor),
+                keywords: arg_keywords(arg),
+            });
+        }
+    }
+
+    entries
+}
+
+/// Words of a command: its command chain, flags, aliases and help.
+fn command_keywords(command: &DocCommand) -> String {
+    let mut words = vec![command.cmd_chain.as_str(), command.flags.as_str()];
+    words.extend(command.aliases.iter().map(String::as_str));
+    words.push(&command.description);
+
+    keywords(&words)
+}
+
+/// Words of an argument: its flags, aliases, help, environment variable and
+/// possible values.
+fn arg_keywords(arg: &DocArg) -> String {
+    let mut words = vec![arg.flags.as_str()];
+    words.extend(arg.aliases.iter().map(String::as_str));
+    words.push(&arg.description);
+    if let Some(env) = &arg.env {
+        words.push(env);
+    }
+    for value in &arg.possible_values {
+        words.push(&value.name);
+        words.push(&value.description);
+    }
+
+    keywords(&words)
+}
+
+fn keywords(words: &[&str]) -> String {
+    text::flatten(&words.join(" ")).to_lowercase()
+}
+
+/// Serialize `entries` so they can be embedded in a `<script>` element, which
+/// ends at the first `</script`, whatever the quotes around it.
+fn to_json(entries: &[Entry]) -> serde_json::Result<String> {
+    Ok(serde_json::to_string(entries)?.replace('<', "\\u003c"))
+}
@@ -0,0 +1,189 @@
+//! Multi-page static site backend.
+//!
+//! Writes one HTML page per command plus an `index.html` listing all of them,
+//! and the search index they share. Every page lives in the same directory and only uses relative links, so the
+//! site works from `file://` as well as from any subpath of a web server.
+
+use std::collections::HashMap;
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use serde_derive::Serialize;
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use crate::page::Headed;
+use crate::slug::FileNames;
+use crate::DocPage;
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use crate::{nav, search, DocArg, DocCommand, DocSubcommand, Result};
+
+/// File names, without extension, taken by the index and the search index.
+const RESERVED: &[&str] = &["index", "search-index"];
+
+/// A single rendered page of the static site.
+#[derive(Clone, Debug)]
+pub struct SitePage {
+    /// File name of the page, e.g. `mycli-sub.html`, or `search-index.js` for
+    /// the search index.
+    ///
+    /// Names are unique and only contain lowercase ASCII letters, digits, `_`
+    /// and `-`, whatever the names of the commands.
+    pub name: String,
+    /// HTML source of the page, or JavaScript source of the search index.
+    pub content: String,
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct Link {
+    name: String,
+    href: String,
+}
+
+/// Data of the `site-page` template.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct CommandPage {
+    pub(crate) command: Headed<DocCommand>,
+    pub(crate) breadcrumbs: Vec<Link>,
+    pub(crate) children: Vec<Child>,
+    pub(crate) inherited: Vec<InheritedArg>,
+}
+
+/// A subcommand, with a link to its page.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct Child {
+    #[serde(flatten)]
+    command: DocSubcommand,
+    href: String,
+}
+
+/// A global argument, with a link to the page of the command defining it.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct InheritedArg {
+    arg: DocArg,
+    link: Link,
+}
+
+/// Data of the `site-index` template.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[derive(Serialize, Clone, Debug)]
+pub(crate) struct IndexPage<'a> {
+    pub(crate) main: &'a DocCommand,
+    pub(crate) pages: Vec<Link>,
+    /// The command tree, rooted at `main`.
+    pub(crate) nav: Vec<nav::NavItem>,
+}
+
+/// Render the index and one page for every command in `page`.
+///
+/// The template engine renders each page through `render_index` and
+/// `render_page`.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+pub(crate) fn render<I, C>(page: &DocPage, render_index: I, mut render_page: C) -> Result<Vec<SitePage>>
+where
+    I: FnOnce(&IndexPage) -> Result<String>,
+    C: FnMut(&CommandPage) -> Result<String>,
+{
+    let commands = std::iter::once(&page.main).chain(page.subcommands.iter());
+    let files = FileNames::new(page, RESERVED);
+
+    let index = IndexPage {
+        main: &page.main,
+        pages: commands
+            .clone()
+            .map(|t| link(&files, &t.cmd_chain, &t.cmd_chain))
+            .collect(),
+        nav: nav::items(std::slice::from_ref(&page.tree), 1, |t| file_name(&files, &t.cmd_chain)),
+    };
+    let mut pages = vec![SitePage {
+        name: "index.html".to_string(),
+        content: render_index(&index)?,
+    }];
+
+    for data in commands {
+        pages.push(SitePage {
+            name: file_name(&files, &data.cmd_chain),
+            content: render_page(&command_page(&files, data))?,
+        });
+    }
+    pages.push(SitePage {
+        name: search::SITE_FILE.to_string(),
+        content: search::site_script(page, |t| file_name(&files, &t.cmd_chain))?,
+    });
+
+    Ok(pages)
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+fn command_page(files: &FileNames, data: &DocCommand) -> CommandPage {
+    // Build a link for every ancestor, e.g. `mycli`, `mycli sub`, `mycli sub deploy`.
+    // The binary name can have several words, which have no page of their own
+    let names = data.cmd_chain.split(' ').collect::<Vec<&str>>();
+    let mut breadcrumbs = Vec::new();
+    let mut start = 0;
+    for i in 1..=names.len() {
+        let cmd_chain = names[..i].join(" ");
+        if files.get(&cmd_chain).is_some() {
+            breadcrumbs.push(link(files, &names[start..i].join(" "), &cmd_chain));
+            start = i;
+        }
+    }
+
+    let children = data
+        .commands
+        .iter()
+        .map(|t| Child {
+            command: t.clone(),
+            href: file_name(files, &format!("{} {}", data.cmd_chain, t.name)),
+        })
+        .collect();
+
+    let inherited = data
+        .inherited
+        .iter()
+        .map(|t| InheritedArg {
+            arg: t.arg.clone(),
+            link: link(files, &t.cmd_chain, &t.cmd_chain),
+        })
+        .collect();
+
+    // The subcommands and inherited arguments are listed by the page itself,
+    // with links to other pages
+    let mut command = data.clone();
+    command.commands.clear();
+    command.inherited.clear();
+
+    CommandPage {
+        // Everything on the page is under its `<h1>`
+        command: Headed::new(command, 2),
+        breadcrumbs,
+        children,
+        inherited,
+    }
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+fn link(files: &FileNames, name: &str, cmd_chain: &str) -> Link {
+    Link {
+        name: name.to_string(),
+        href: file_name(files, cmd_chain),
+    }
+}
+
+/// Command chain documented by each page, by file name.
+pub(crate) fn commands(page: &DocPage) -> HashMap<String, String> {
+    let files = FileNames::new(page, RESERVED);
+    std::iter::once(&page.main)
+        .chain(&page.subcommands)
+        .map(|t| (file_name(&files, &t.cmd_chain), t.cmd_chain.clone()))
+        .collect()
+}
+
+/// File name of the page of a command, e.g. `mycli-sub.html` for `mycli sub`.
+///
+/// Commands without a page of their own link to the index.
+fn file_name(files: &FileNames, cmd_chain: &str) -> String {
+    format!("{}.html", files.get(cmd_chain).unwrap_or("index"))
+}
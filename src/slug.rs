@@ -0,0 +1,122 @@
+//! Anchors of the commands and arguments in the single HTML page, and file
+//! names of the pages documenting each command.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::DocPage;
+
+/// IDs used by the bundled templates, which no anchor may take.
+pub(crate) const TEMPLATE_IDS: &[&str] =
+    &["content", "menu", "menu-content", "search-index", "search-input", "search-status", "search-results"];
+
+/// Hand out anchors that are unique across a page.
+///
+/// Anchors only contain lowercase ASCII letters, digits, `_` and `-`, so they
+/// are valid fragments and IDs without any escaping. The page is walked in the
+/// same order on every run, so the same command gets the same anchor as long
+/// as the command tree doesn't change.
+#[derive(Debug)]
+pub(crate) struct Slugger {
+    used: HashSet<String>,
+}
+
+impl Slugger {
+    /// A slugger which never hands out any of `names`.
+    pub(crate) fn reserving(names: &[&str]) -> Self {
+        Slugger {
+            used: names.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Anchor of a command, e.g. `mycli-deploy` for `mycli deploy`.
+    pub(crate) fn command(&mut self, cmd_chain: &str) -> String {
+        self.unique(sanitize(cmd_chain))
+    }
+
+    /// Anchor of an argument of the command anchored at `command`, e.g.
+    /// `mycli-deploy--force` for `--force`.
+    ///
+    /// Command anchors never contain `--`, so argument anchors can't take theirs.
+    pub(crate) fn arg(&mut self, command: &str, name: &str) -> String {
+        let name = match sanitize(name) {
+            name if name.is_empty() => "arg".to_string(),
+            name => name,
+        };
+        self.unique(format!("{}--{}", command, name))
+    }
+
+    /// Reserve `slug`, adding `-2`, `-3`... when it is already taken.
+    fn unique(&mut self, slug: String) -> String {
+        let slug = match slug.is_empty() {
+            true => "section".to_string(),
+            false => slug,
+        };
+
+        let mut candidate = slug.clone();
+        let mut n = 1;
+        while self.used.contains(&candidate) {
+            n += 1;
+            candidate = format!("{}-{}", slug, n);
+        }
+        self.used.insert(candidate.clone());
+
+        candidate
+    }
+}
+
+/// File names, without extension, of the pages documenting each command.
+///
+/// Names are unique and follow the same rules as anchors, so they are safe on
+/// every file system and need no escaping in links. `mycli a-b` and `mycli a b`
+/// get `mycli-a-b` and `mycli-a-b-2`.
+#[derive(Debug)]
+pub(crate) struct FileNames {
+    names: HashMap<String, String>,
+}
+
+impl FileNames {
+    /// Name the page of every command in `page`, never using one of `reserved`.
+    pub(crate) fn new(page: &DocPage, reserved: &[&str]) -> Self {
+        let mut slugger = Slugger::reserving(reserved);
+        let names = std::iter::once(&page.main)
+            .chain(&page.subcommands)
+            .map(|t| (t.cmd_chain.clone(), slugger.command(&t.cmd_chain)))
+            .collect();
+
+        FileNames { names }
+    }
+
+    /// Name of the page documenting the `cmd_chain` command, if it has one.
+    pub(crate) fn get(&self, cmd_chain: &str) -> Option<&str> {
+        self.names.get(cmd_chain).map(String::as_str)
+    }
+}
+
+/// Turn `text` into words of lowercase ASCII letters, digits and `_`, joined
+/// by single dashes, e.g. `My.CLI v2` becomes `my-cli-v2`.
+///
+/// Other characters separate words, except non ASCII letters and digits, which
+/// are written as their hexadecimal code point so that `café` and `cafè` still
+/// get different anchors.
+fn sanitize(text: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word.push(c.to_ascii_lowercase());
+            continue;
+        }
+
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c.is_alphanumeric() {
+            words.push(format!("{:x}", c as u32));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words.join("-")
+}
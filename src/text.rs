@@ -0,0 +1,299 @@
+//! Structure of help texts, shared by every backend.
+//!
+//! Help texts are plain text, written for a terminal. They are split into
+//! blocks the way a reader would see them:
+//!
+//! - paragraphs, separated by blank lines, whose lines are joined back together
+//!   since they were only wrapped to fit the terminal;
+//! - lists, whose items start with `-` or `*`;
+//! - code blocks, indented by at least four spaces or a tab.
+//!
+//! Bare `http://` and `https://` URLs in paragraphs and list items are links.
+
+/// A block of a help text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Block {
+    /// Text of a paragraph, on a single line.
+    Paragraph(String),
+    /// Text of every item of a list, each on a single line.
+    List(Vec<String>),
+    /// Lines of a code block, without their common indentation.
+    Code(Vec<String>),
+}
+
+/// A run of text of a paragraph or list item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Span<'a> {
+    Text(&'a str),
+    Link(&'a str),
+}
+
+/// Split a help text into blocks.
+pub(crate) fn blocks(text: &str) -> Vec<Block> {
+    let lines = text.lines().map(str::trim_end).collect::<Vec<&str>>();
+
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.is_empty() {
+            i += 1;
+        } else if is_code(line) {
+            // Blank lines belong to the block when more code follows them
+            let start = i;
+            let mut end = i;
+            while i < lines.len() && (lines[i].is_empty() || is_code(lines[i])) {
+                if !lines[i].is_empty() {
+                    end = i + 1;
+                }
+                i += 1;
+            }
+            i = end;
+            blocks.push(Block::Code(dedent(&lines[start..end])));
+        } else if let Some(item) = list_item(line) {
+            let mut items = vec![item.to_string()];
+            i += 1;
+            while i < lines.len() {
+                let line = lines[i];
+                if let Some(item) = list_item(line) {
+                    items.push(item.to_string());
+                } else if line.is_empty() {
+                    // Blank lines between items don't end the list
+                    match lines[i..].iter().find(|l| !l.is_empty()) {
+                        Some(next) if list_item(next).is_some() => {}
+                        _ => break,
+                    }
+                } else if let Some(last) = items.last_mut() {
+                    last.push(' ');
+                    last.push_str(line.trim());
+                }
+                i += 1;
+            }
+            blocks.push(Block::List(items));
+        } else {
+            let mut words = vec![line.trim()];
+            i += 1;
+            while i < lines.len() {
+                let line = lines[i];
+                if line.is_empty() || is_code(line) || list_item(line).is_some() {
+                    break;
+                }
+                words.push(line.trim());
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(words.join(" ")));
+        }
+    }
+
+    blocks
+}
+
+/// Split the text of a paragraph or list item into text and links.
+pub(crate) fn spans(text: &str) -> Vec<Span<'_>> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while let Some(start) = find_url(rest) {
+        let len = rest[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | '`'))
+            .unwrap_or(rest.len() - start);
+        let mut url = &rest[start..start + len];
+        // Punctuation ending a sentence is not part of the URL
+        loop {
+            let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'']);
+            let trimmed = match trimmed.ends_with(')') && !trimmed.contains('(') {
+                true => &trimmed[..trimmed.len() - 1],
+                false => trimmed,
+            };
+            if trimmed == url {
+                break;
+            }
+            url = trimmed;
+        }
+
+        // A scheme alone is not a link
+        if url.ends_with("//") {
+            spans.push(Span::Text(&rest[..start + len]));
+            rest = &rest[start + len..];
+            continue;
+        }
+
+        if start > 0 {
+            spans.push(Span::Text(&rest[..start]));
+        }
+        spans.push(Span::Link(url));
+        rest = &rest[start + url.len()..];
+    }
+    if !rest.is_empty() {
+        spans.push(Span::Text(rest));
+    }
+
+    spans
+}
+
+/// Text of a help text on a single line, e.g. for the summary of a command.
+pub(crate) fn flatten(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn find_url(text: &str) -> Option<usize> {
+    text.match_indices("http")
+        .map(|(i, _)| i)
+        .find(|&i| {
+            let word_start = text[..i]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !c.is_alphanumeric());
+            word_start && (text[i..].starts_with("http://") || text[i..].starts_with("https://"))
+        })
+}
+
+fn is_code(line: &str) -> bool {
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+/// Text of a list item, if `line` starts one.
+fn list_item(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .map(str::trim)
+}
+
+/// Remove the indentation shared by every non blank line.
+fn dedent(lines: &[&str]) -> Vec<String> {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_default().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Block::*;
+    use Span::*;
+
+    fn paragraph(text: &str) -> Block {
+        Paragraph(text.to_string())
+    }
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn paragraphs_are_unwrapped() {
+        assert_eq!(
+            blocks("Deploy the\n  application.\n\n\nSecond one.\n"),
+            [paragraph("Deploy the application."), paragraph("Second one.")]
+        );
+        assert_eq!(blocks(""), []);
+        assert_eq!(blocks("\n  \n"), []);
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            blocks("Targets:\n- staging, for\n  previews\n* production"),
+            [paragraph("Targets:"), List(lines(&["staging, for previews", "production"]))]
+        );
+    }
+
+    #[test]
+    fn lazy_list_continuation() {
+        assert_eq!(
+            blocks("- first item\nwrapped without indent\n- second"),
+            [List(lines(&["first item wrapped without indent", "second"]))]
+        );
+    }
+
+    #[test]
+    fn blank_lines_inside_lists() {
+        assert_eq!(
+            blocks("- one\n\n- two\n\nAfter the list."),
+            [List(lines(&["one", "two"])), paragraph("After the list.")]
+        );
+    }
+
+    #[test]
+    fn code_blocks() {
+        assert_eq!(
+            blocks("Example:\n\n    mycli deploy\n\n      --fast\n\n\nDone."),
+            [
+                paragraph("Example:"),
+                Code(lines(&["mycli deploy", "", "  --fast"])),
+                paragraph("Done.")
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_blank_lines_leave_code_blocks() {
+        assert_eq!(blocks("    mycli deploy\n\n  \n"), [Code(lines(&["mycli deploy"]))]);
+    }
+
+    #[test]
+    fn tab_indented_code() {
+        assert_eq!(
+            blocks("Run:\n\tmycli deploy\n\t  --fast"),
+            [paragraph("Run:"), Code(lines(&["mycli deploy", "  --fast"]))]
+        );
+    }
+
+    #[test]
+    fn links() {
+        assert_eq!(
+            spans("See https://example.com/docs for more"),
+            [Text("See "), Link("https://example.com/docs"), Text(" for more")]
+        );
+        assert_eq!(spans("http://a.io"), [Link("http://a.io")]);
+        assert_eq!(spans("No link here"), [Text("No link here")]);
+    }
+
+    #[test]
+    fn trailing_punctuation_leaves_links() {
+        assert_eq!(
+            spans("See https://example.com/docs."),
+            [Text("See "), Link("https://example.com/docs"), Text(".")]
+        );
+        assert_eq!(
+            spans("'https://example.com/',"),
+            [Text("'"), Link("https://example.com/"), Text("',")]
+        );
+        assert_eq!(
+            spans("Use `https://x.io`"),
+            [Text("Use `"), Link("https://x.io"), Text("`")]
+        );
+    }
+
+    #[test]
+    fn parentheses_around_links() {
+        assert_eq!(
+            spans("(see https://example.com/a)."),
+            [Text("(see "), Link("https://example.com/a"), Text(").")]
+        );
+        assert_eq!(
+            spans("https://en.wikipedia.org/wiki/Rust_(language)."),
+            [Link("https://en.wikipedia.org/wiki/Rust_(language)"), Text(".")]
+        );
+    }
+
+    #[test]
+    fn not_links() {
+        assert_eq!(spans("https:// alone"), [Text("https://"), Text(" alone")]);
+        assert_eq!(spans("xhttps://example.com"), [Text("xhttps://example.com")]);
+    }
+
+    #[test]
+    fn flattened() {
+        assert_eq!(flatten("Deploy\n  the\tapplication "), "Deploy the application");
+    }
+}
@@ -0,0 +1,44 @@
+//! Themes of the HTML pages.
+
+/// Rules of the stylesheet, written against the custom properties of the themes.
+const BASE: &str = include_str!("../data/style.css");
+
+/// Stylesheet of the HTML pages when no theme is selected.
+pub(crate) const DEFAULT_STYLE: &str = concat!(
+    include_str!("../data/themes/default.css"),
+    include_str!("../data/style.css")
+);
+
+/// Color schemes and fonts bundled for the HTML pages.
+///
+/// A theme only defines CSS custom properties, such as `--background`,
+/// `--accent-color` or `--font-family`, which the stylesheet uses. Custom CSS
+/// added with [`ClapShow::css`](crate::ClapShow::css) can override any of them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Theme {
+    /// Monospace fonts and purple borders, switching to dark colors when the
+    /// reader's system prefers them, with `prefers-color-scheme`.
+    #[default]
+    Default,
+    /// Sans-serif fonts and light colors, with few borders.
+    Light,
+    /// Dark colors, whatever the reader's system prefers.
+    Dark,
+}
+
+impl Theme {
+    /// The custom properties defined by the theme.
+    fn properties(self) -> &'static str {
+        match self {
+            Theme::Default => include_str!("../data/themes/default.css"),
+            Theme::Light => include_str!("../data/themes/light.css"),
+            Theme::Dark => include_str!("../data/themes/dark.css"),
+        }
+    }
+
+    /// The whole stylesheet of the theme.
+    pub(crate) fn stylesheet(self) -> String {
+        format!("{}{}", self.properties(), BASE)
+    }
+}
@@ -0,0 +1,323 @@
+//! Usage lines of a command, split into typed tokens.
+//!
+//! The lines come from clap itself, so they follow every rule clap applies
+//! (required arguments, groups, `last` arguments, conflicting subcommands...),
+//! and are then tokenized so the backends can highlight each part.
+
+use clap::Command;
+
+use crate::{DocUsage, DocUsageToken, DocUsageTokenKind};
+
+/// Characters ending a flag or a placeholder name.
+const DELIMITERS: &[char] = &['[', ']', '<', '>', '{', '}', '|', '='];
+
+/// Every usage line clap renders for `command`.
+///
+/// `subcommands` are the names of the subcommands leading to `command` from the
+/// root command, `command` included, so they can be told apart from the binary
+/// name, which can have several words.
+pub(crate) fn usages(command: &Command, subcommands: &[String]) -> Vec<DocUsage> {
+    let rendered = command.clone().render_usage().to_string();
+    let rendered = rendered.strip_prefix("Usage:").unwrap_or(&rendered);
+
+    let bin_name = match command.get_bin_name() {
+        Some(name) => name.to_string(),
+        None => command.get_name().to_string(),
+    };
+    let mut binary = bin_name.as_str();
+    for name in subcommands.iter().rev() {
+        binary = binary
+            .strip_suffix(name.as_str())
+            .map(str::trim_end)
+            .unwrap_or(binary);
+    }
+
+    rendered
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let tokens = tokenize(line, binary, subcommands);
+            DocUsage {
+                text: line.to_string(),
+                tokens,
+            }
+        })
+        .collect()
+}
+
+/// `line` without the binary name and the subcommands, e.g. `[OPTIONS] <FILE>`
+/// for `mycli deploy [OPTIONS] <FILE>`.
+///
+/// Pacman-style subcommands are left out along with their flags, e.g.
+/// `{sync|-S}`.
+pub(crate) fn without_chain(line: &DocUsage) -> String {
+    let text = line.text.as_str();
+
+    let mut out = String::new();
+    let (mut kept, mut offset) = (0, 0);
+    for token in &line.tokens {
+        let (start, end) = (offset, offset + token.text.len());
+        offset = end;
+        let (start, end) = match token.kind {
+            DocUsageTokenKind::Binary => (start, end),
+            DocUsageTokenKind::Subcommand => match (text[..start].ends_with('{'), text[end..].find('}')) {
+                (true, Some(close)) => (start - 1, end + close + 1),
+                _ => (start, end),
+            },
+            _ => continue,
+        };
+        out.push_str(&text[kept..start]);
+        kept = end;
+    }
+    out.push_str(&text[kept..]);
+
+    out.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn tokenize(line: &str, binary: &str, subcommands: &[String]) -> Vec<DocUsageToken> {
+    let mut tokens = Vec::new();
+
+    // Every line starts with the binary name
+    let mut rest = match line.strip_prefix(binary) {
+        Some(rest) => {
+            tokens.push(token(DocUsageTokenKind::Binary, binary));
+            rest
+        }
+        None => line,
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = match c {
+            '[' | ']' => (DocUsageTokenKind::Optional, 1),
+            _ if rest.starts_with("...") => (DocUsageTokenKind::Repetition, 3),
+            '<' | '>' | '{' | '}' | '|' | '=' => (DocUsageTokenKind::Text, 1),
+            c if c.is_whitespace() => (DocUsageTokenKind::Text, c.len_utf8()),
+            _ => {
+                let len = rest
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        c.is_whitespace() || DELIMITERS.contains(&c) || rest[i..].starts_with("...")
+                    })
+                    .map_or(rest.len(), |(i, _)| i)
+                    .max(c.len_utf8());
+                match &rest[..len] {
+                    // Separates the `last` arguments
+                    "--" => (DocUsageTokenKind::Text, len),
+                    word if word.starts_with('-') => (DocUsageTokenKind::Flag, len),
+                    _ => (DocUsageTokenKind::Placeholder, len),
+                }
+            }
+        };
+
+        match kind {
+            DocUsageTokenKind::Text => push_text(&mut tokens, &rest[..len]),
+            kind => tokens.push(token(kind, &rest[..len])),
+        }
+        rest = &rest[len..];
+    }
+
+    tag_subcommands(&mut tokens, subcommands);
+    tokens
+}
+
+/// Tag the names of the `subcommands` leading to the command, in order.
+///
+/// They follow the binary name, after the required arguments of their parents
+/// if any, e.g. `mycli <--file <FILE>|--stdin> deploy`, and pacman-style ones
+/// come with their flags, e.g. `{deploy|-D}`. Subcommands are never inside
+/// `[...]` or `<...>`, so an argument named like one isn't taken for it.
+fn tag_subcommands(tokens: &mut [DocUsageToken], subcommands: &[String]) {
+    let mut names = subcommands.iter().peekable();
+    let mut depth = 0;
+    for token in tokens {
+        match token.kind {
+            DocUsageTokenKind::Optional if token.text == "[" => depth += 1,
+            DocUsageTokenKind::Optional => depth -= 1,
+            DocUsageTokenKind::Text => {
+                depth += token.text.matches('<').count() as isize;
+                depth -= token.text.matches('>').count() as isize;
+            }
+            DocUsageTokenKind::Placeholder if depth == 0 && names.peek() == Some(&&token.text) => {
+                token.kind = DocUsageTokenKind::Subcommand;
+                names.next();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn token(kind: DocUsageTokenKind, text: &str) -> DocUsageToken {
+    DocUsageToken {
+        kind,
+        text: text.to_string(),
+    }
+}
+
+/// Append `text` to the previous token when it is text too, so punctuation
+/// and spaces end up in as few tokens as possible.
+fn push_text(tokens: &mut Vec<DocUsageToken>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    match tokens.last_mut() {
+        Some(last) if last.kind == DocUsageTokenKind::Text => last.text.push_str(text),
+        _ => tokens.push(token(DocUsageTokenKind::Text, text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{Arg, ArgGroup, Command};
+
+    use super::*;
+    use DocUsageTokenKind::*;
+
+    /// Usage lines of the subcommand of `command` found at `path`.
+    fn usages_of(mut command: Command, path: &[&str]) -> Vec<DocUsage> {
+        command.build();
+        let mut target = &command;
+        for name in path {
+            target = target.find_subcommand(name).unwrap();
+        }
+        let names = path.iter().map(|t| t.to_string()).collect::<Vec<String>>();
+        usages(target, &names)
+    }
+
+    /// Every token but the text between them.
+    fn tokens(usage: &DocUsage) -> Vec<(DocUsageTokenKind, &str)> {
+        usage
+            .tokens
+            .iter()
+            .filter(|t| t.kind != Text)
+            .map(|t| (t.kind, t.text.as_str()))
+            .collect()
+    }
+
+    fn source() -> Command {
+        Command::new("mycli")
+            .arg(Arg::new("file").long("file"))
+            .arg(Arg::new("stdin").long("stdin").num_args(0))
+            .group(ArgGroup::new("source").args(["file", "stdin"]).required(true))
+    }
+
+    #[test]
+    fn tokens_cover_the_line() {
+        let command = Command::new("mycli").arg(Arg::new("files").num_args(1..).last(true));
+        let usages = usages_of(command, &[]);
+
+        assert_eq!(usages[0].text, "mycli [-- <files>...]");
+        let text = usages[0].tokens.iter().map(|t| t.text.as_str()).collect::<String>();
+        assert_eq!(text, usages[0].text);
+        assert_eq!(
+            tokens(&usages[0]),
+            [
+                (Binary, "mycli"),
+                (Optional, "["),
+                (Placeholder, "files"),
+                (Repetition, "..."),
+                (Optional, "]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_every_line() {
+        let command = Command::new("mycli").subcommand(
+            Command::new("deploy")
+                .args_conflicts_with_subcommands(true)
+                .arg(Arg::new("force").long("force").num_args(0))
+                .subcommand(Command::new("now")),
+        );
+        let usages = usages_of(command, &["deploy"]);
+
+        let lines = usages.iter().map(|t| t.text.as_str()).collect::<Vec<&str>>();
+        assert_eq!(lines, ["mycli deploy [OPTIONS]", "mycli deploy <COMMAND>"]);
+        for usage in &usages {
+            assert_eq!(tokens(usage)[..2], [(Binary, "mycli"), (Subcommand, "deploy")]);
+        }
+        assert_eq!(without_chain(&usages[1]), "<COMMAND>");
+    }
+
+    #[test]
+    fn binary_name_can_have_several_words() {
+        let command = Command::new("mycli")
+            .bin_name("cargo mycli")
+            .subcommand(Command::new("deploy").arg(Arg::new("target").required(true)));
+        let usages = usages_of(command, &["deploy"]);
+
+        assert_eq!(
+            tokens(&usages[0]),
+            [
+                (Binary, "cargo mycli"),
+                (Subcommand, "deploy"),
+                (Placeholder, "target"),
+            ]
+        );
+        assert_eq!(without_chain(&usages[0]), "<target>");
+    }
+
+    #[test]
+    fn subcommands_can_follow_the_arguments_of_their_parents() {
+        let command = source().subcommand(
+            Command::new("deploy")
+                .arg(Arg::new("target").required(true))
+                .subcommand(Command::new("now")),
+        );
+        let usages = usages_of(command.clone(), &["deploy"]);
+
+        assert_eq!(usages[0].text, "mycli <--file <file>|--stdin> deploy <target> [COMMAND]");
+        assert_eq!(
+            tokens(&usages[0])[..5],
+            [
+                (Binary, "mycli"),
+                (Flag, "--file"),
+                (Placeholder, "file"),
+                (Flag, "--stdin"),
+                (Subcommand, "deploy"),
+            ]
+        );
+        assert_eq!(without_chain(&usages[0]), "<--file <file>|--stdin> <target> [COMMAND]");
+
+        let usages = usages_of(command, &["deploy", "now"]);
+
+        assert_eq!(usages[0].text, "mycli deploy <target> now");
+        assert_eq!(
+            tokens(&usages[0]),
+            [
+                (Binary, "mycli"),
+                (Subcommand, "deploy"),
+                (Placeholder, "target"),
+                (Subcommand, "now"),
+            ]
+        );
+        assert_eq!(without_chain(&usages[0]), "<target>");
+    }
+
+    #[test]
+    fn flag_subcommands_are_tagged() {
+        let command = source().subcommand(Command::new("deploy").short_flag('D').long_flag("deploy"));
+        let usages = usages_of(command, &["deploy"]);
+
+        assert_eq!(usages[0].text, "mycli <--file <file>|--stdin> {deploy|--deploy|-D}");
+        assert_eq!(
+            tokens(&usages[0])[4..],
+            [(Subcommand, "deploy"), (Flag, "--deploy"), (Flag, "-D")]
+        );
+        assert_eq!(without_chain(&usages[0]), "<--file <file>|--stdin>");
+    }
+
+    #[test]
+    fn arguments_are_not_taken_for_subcommands() {
+        let command = Command::new("mycli")
+            .subcommand_negates_reqs(true)
+            .arg(Arg::new("deploy").required(true))
+            .subcommand(Command::new("deploy"));
+        let usages = usages_of(command, &["deploy"]);
+
+        let kinds = tokens(&usages[0]);
+        assert_eq!(kinds.iter().filter(|t| t.0 == Subcommand).count(), 1, "{:?}", kinds);
+        assert_eq!(kinds.last(), Some(&(Subcommand, "deploy")));
+    }
+}
@@ -0,0 +1,91 @@
+//! Aliases and pacman-style flags are documented next to the primary names.
+
+use clap::{Arg, Command};
+use clap_show::ClapShow;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("pacman")
+        .subcommand(
+            Command::new("sync")
+                .about("Synchronize packages")
+                .short_flag('S')
+                .long_flag("sync")
+                .visible_alias("sy")
+                .alias("syn")
+                .visible_short_flag_alias('Y')
+                .arg(
+                    Arg::new("refresh")
+                        .long("refresh")
+                        .short('y')
+                        .visible_alias("update")
+                        .visible_short_alias('u')
+                        .alias("fresh")
+                        .num_args(0),
+                ),
+        )
+        .subcommand(Command::new("query").short_flag('Q'))
+}
+
+#[test]
+fn extracts_visible_aliases() {
+    let page = clap_show::extract(&command());
+    let sync = &page.subcommands[0];
+
+    assert_eq!(sync.flags, "-S, --sync");
+    assert_eq!(sync.aliases, ["-Y", "sy"]);
+    assert_eq!(sync.options[0].aliases, ["-u", "--update"]);
+    assert_eq!(page.main.commands[0].flags, "-S, --sync");
+    assert_eq!(page.main.commands[0].aliases, ["-Y", "sy"]);
+    assert_eq!(page.main.commands[1].flags, "-Q");
+    assert!(page.main.commands[1].aliases.is_empty());
+}
+
+#[test]
+fn hidden_aliases_are_optional() {
+    let command = command();
+
+    let markdown = ClapShow::new(&command).render_markdown().unwrap();
+    assert!(!markdown.contains("`syn`"));
+    assert!(!markdown.contains("--fresh"));
+
+    let markdown = ClapShow::new(&command).hidden_aliases(true).render_markdown().unwrap();
+    assert!(markdown.contains("Aliases: `-Y`, `sy`, `syn`"));
+    assert!(markdown.contains("Aliases: `-u`, `--update`, `--fresh`"));
+}
+
+#[test]
+fn markdown_shows_aliases() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+
+    assert!(markdown.contains("| `sync, -S, --sync` | Synchronize packages<br>Aliases: `-Y`, `sy` |"));
+    assert!(markdown.contains("Flags: `-S, --sync`\n\nAliases: `-Y`, `sy`\n"));
+    assert!(markdown.contains("| `-y, --refresh` | Aliases: `-u`, `--update` |"));
+}
+
+#[test]
+fn man_pages_show_aliases() {
+    let pages = ClapShow::new(&command()).render_man_pages().unwrap();
+    let sync = pages.iter().find(|p| p.name == "pacman-sync.1").unwrap();
+
+    assert!(sync
+        .content
+        .contains(".SH ALIASES\n\\fB\\-S\\fR, \\fB\\-\\-sync\\fR, \\fB\\-Y\\fR, \\fBsy\\fR\n"));
+    assert!(sync.content.contains("[aliases: \\fB\\-u\\fR, \\fB\\-\\-update\\fR]"));
+    assert!(pages[0].content.contains("\\fBsync\\fR, \\fB\\-S\\fR, \\fB\\-\\-sync\\fR"));
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn html_shows_aliases() {
+    for engine in common::engines() {
+        let html = ClapShow::new(&command()).engine(engine).render().unwrap();
+
+        assert!(html.contains("<code>sync, -S, --sync</code>"), "{:?}", engine);
+        assert!(html.contains("Flags: <code>-S, --sync</code>"), "{:?}", engine);
+        assert!(html.contains("Aliases: <code>-Y</code> <code>sy</code>"), "{:?}", engine);
+        assert!(html.contains("Aliases: <code>-u</code> <code>--update</code>"), "{:?}", engine);
+        assert!(!html.contains("--fresh"), "{:?}", engine);
+    }
+}
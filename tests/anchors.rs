@@ -0,0 +1,112 @@
+//! Anchors are unique, URL safe and stable.
+
+mod common;
+
+use std::collections::HashSet;
+
+use clap::{Arg, Command};
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use clap_show::ClapShow;
+
+fn command() -> Command {
+    Command::new("my.cli")
+        .arg(Arg::new("force").long("force"))
+        .subcommand(
+            Command::new("deploy")
+                .arg(Arg::new("force").long("force").short('f'))
+                .arg(Arg::new("target")),
+        )
+        .subcommand(Command::new("de/ploy"))
+        .subcommand(Command::new("De.Ploy"))
+        .subcommand(Command::new("café"))
+}
+
+fn anchors(page: &clap_show::DocPage) -> Vec<String> {
+    let mut anchors = Vec::new();
+    for command in std::iter::once(&page.main).chain(&page.subcommands) {
+        anchors.push(command.anchor.clone());
+        for arg in command.arguments.iter().chain(&command.options) {
+            anchors.push(arg.anchor.clone());
+        }
+    }
+    anchors
+}
+
+#[test]
+fn deep_links_to_arguments() {
+    let page = clap_show::extract(&command());
+    let deploy = &page.subcommands[0];
+
+    assert_eq!(page.main.anchor, "my-cli");
+    assert_eq!(page.main.options[0].anchor, "my-cli--force");
+    assert_eq!(deploy.anchor, "my-cli-deploy");
+    assert_eq!(deploy.options[0].anchor, "my-cli-deploy--force");
+    assert_eq!(deploy.arguments[0].anchor, "my-cli-deploy--target");
+}
+
+#[test]
+fn unique_and_url_safe() {
+    let anchors = anchors(&clap_show::extract(&command()));
+
+    let unique = anchors.iter().collect::<HashSet<_>>();
+    assert_eq!(unique.len(), anchors.len(), "{:?}", anchors);
+    for anchor in &anchors {
+        assert!(
+            anchor.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'),
+            "{}",
+            anchor
+        );
+    }
+}
+
+#[test]
+fn stable_between_runs() {
+    assert_eq!(
+        anchors(&clap_show::extract(&command())),
+        anchors(&clap_show::extract(&command()))
+    );
+}
+
+/// A command whose anchors would take the IDs used by the templates.
+fn clashing() -> Command {
+    Command::new("search")
+        .arg(Arg::new("input").long("input"))
+        .subcommand(Command::new("index"))
+        .subcommand(Command::new("menu"))
+}
+
+#[test]
+fn never_take_template_ids() {
+    let page = clap_show::extract(&Command::new("menu").subcommand(Command::new("content")));
+    assert_eq!(page.main.anchor, "menu-2");
+    assert_eq!(page.subcommands[0].anchor, "menu-content-2");
+
+    let anchors = anchors(&clap_show::extract(&clashing()));
+    assert_eq!(anchors[..3], ["search", "search--input", "search--help"]);
+    assert!(!anchors.contains(&"search-index".to_string()), "{:?}", anchors);
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn unique_ids_in_html() {
+    for engine in common::engines() {
+        for html in common::outputs(ClapShow::new(&clashing()).engine(engine)) {
+            let ids: Vec<&str> = html.split(" id=\"").skip(1).map(|t| &t[..t.find('"').unwrap()]).collect();
+            let unique = ids.iter().collect::<HashSet<_>>();
+            assert_eq!(unique.len(), ids.len(), "{:?} {:?}", engine, ids);
+        }
+    }
+}
+
+#[test]
+fn markdown_links_to_headings_with_the_same_slug() {
+    let command = Command::new("mycli").subcommand(Command::new("a.b")).subcommand(
+        Command::new("ab")
+            .arg(Arg::new("region").long("region").global(true))
+            .subcommand(Command::new("run")),
+    );
+    let markdown = clap_show::render_markdown(&command).unwrap();
+
+    assert!(markdown.contains("- [mycli a.b](#mycli-ab)\n- [mycli ab](#mycli-ab-1)\n"));
+    assert!(markdown.contains("| `--region <region>` | Defined in: [mycli ab](#mycli-ab-1) |"));
+}
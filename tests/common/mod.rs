@@ -0,0 +1,26 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use clap_show::{ClapShow, Engine};
+
+/// Every template engine enabled by the features of the build.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+pub fn engines() -> Vec<Engine> {
+    vec![
+        #[cfg(feature = "handlebars")]
+        Engine::Handlebars,
+        #[cfg(feature = "ramhorns")]
+        Engine::Ramhorns,
+    ]
+}
+
+/// Every HTML output of `builder`: the single page and each site page.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+pub fn outputs(builder: ClapShow) -> Vec<String> {
+    let mut outputs = vec![builder.render().unwrap()];
+    let site = builder.render_site().unwrap();
+    outputs.extend(site.into_iter().filter(|p| p.name.ends_with(".html")).map(|p| p.content));
+    outputs
+}
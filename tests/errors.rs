@@ -0,0 +1,147 @@
+//! Failures are reported as typed errors naming what failed.
+
+use std::io;
+
+use clap::{Arg, Command};
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use clap_show::ClapShow;
+use clap_show::Error;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .subcommand(Command::new("build"))
+        .subcommand(Command::new("deploy").arg(Arg::new("target")))
+}
+
+/// A sink refusing every write.
+struct Closed;
+
+impl io::Write for Closed {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "handlebars")]
+#[test]
+fn missing_param_names_the_failing_command() {
+    // Only the subcommands with arguments call the helper without its parameter
+    let template = "{{#each subcommands as |t|}}{{#if t.arguments}}{{paragraph}}{{/if}}{{/each}}";
+    let err = ClapShow::new(&command())
+        .engine(clap_show::Engine::Handlebars)
+        .template(template)
+        .render()
+        .unwrap_err();
+
+    match &err {
+        Error::MissingParam {
+            command,
+            helper,
+            index,
+        } => {
+            assert_eq!(command, "mycli deploy");
+            assert_eq!(helper, "paragraph");
+            assert_eq!(*index, 0);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+    assert_eq!(
+        err.to_string(),
+        "unable to render `mycli deploy`: helper `paragraph` requires a parameter at index 0"
+    );
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn bad_template_is_named() {
+    for engine in common::engines() {
+        let err = ClapShow::new(&command())
+            .engine(engine)
+            .partial("usage-partial", "{{#each subcommands}}{{#subcommands}}")
+            .render()
+            .unwrap_err();
+
+        match &err {
+            Error::Template { name, .. } => assert_eq!(name, "usage-partial", "{:?}", engine),
+            err => panic!("{:?}: {:?}", engine, err),
+        }
+        assert!(std::error::Error::source(&err).is_some(), "{:?}", engine);
+    }
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn missing_template_file_is_named() {
+    for engine in common::engines() {
+        let err = ClapShow::new(&command())
+            .engine(engine)
+            .template_file("does/not/exist.html")
+            .render()
+            .unwrap_err();
+
+        match &err {
+            Error::Template { name, source } => {
+                assert_eq!(name, "template", "{:?}", engine);
+                let source = source.downcast_ref::<io::Error>().unwrap();
+                assert_eq!(source.kind(), io::ErrorKind::NotFound, "{:?}", engine);
+            }
+            err => panic!("{:?}: unexpected error: {:?}", engine, err),
+        }
+        assert!(err.to_string().starts_with("unable to load template `template`: "));
+    }
+}
+
+#[test]
+fn write_failures_are_io_errors() {
+    let err = clap_show::write_markdown(&command(), &mut Closed).unwrap_err();
+
+    match &err {
+        Error::Io(source) => assert_eq!(source.kind(), io::ErrorKind::BrokenPipe),
+        err => panic!("unexpected error: {:?}", err),
+    }
+    assert_eq!(err.to_string(), "unable to write documentation: closed");
+}
+
+#[test]
+fn unwritable_directory_is_an_io_error() {
+    let file = std::env::temp_dir().join(format!("clap-show-errors-{}", std::process::id()));
+    std::fs::write(&file, "").unwrap();
+
+    let err = clap_show::write_man_pages(&command(), file.join("man")).unwrap_err();
+    std::fs::remove_file(&file).unwrap();
+
+    match &err {
+        Error::Write { command, path, .. } => {
+            assert_eq!(command, "mycli");
+            assert_eq!(path, &file.join("man"));
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+    let message = format!("unable to write `{}` for `mycli`: ", file.join("man").display());
+    assert!(err.to_string().starts_with(&message), "{}", err);
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn unwritable_page_is_named() {
+    let dir = std::env::temp_dir().join(format!("clap-show-errors-page-{}", std::process::id()));
+    // A directory in the way of the page of `mycli deploy`
+    std::fs::create_dir_all(dir.join("mycli-deploy.1")).unwrap();
+
+    let err = clap_show::write_man_pages(&command(), &dir).unwrap_err();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    match &err {
+        Error::Write { command, path, .. } => {
+            assert_eq!(command, "mycli deploy");
+            assert_eq!(path, &dir.join("mycli-deploy.1"));
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
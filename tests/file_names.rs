@@ -0,0 +1,117 @@
+//! Pages get unique, file system safe names, whatever the names of the commands.
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+use std::collections::HashSet;
+
+use clap::Command;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("index")
+        .disable_help_subcommand(true)
+        .subcommand(Command::new("a.b/c"))
+        .subcommand(Command::new("a-b"))
+        .subcommand(Command::new("a").subcommand(Command::new("b")))
+        .subcommand(Command::new("search").subcommand(Command::new("index")))
+}
+
+/// Every `href` of the HTML `page` pointing to another file.
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+fn links(page: &str) -> Vec<&str> {
+    page.split("href=\"")
+        .skip(1)
+        .map(|s| &s[..s.find(['"', '#']).unwrap()])
+        .filter(|href| !href.is_empty() && !href.starts_with("http"))
+        .collect()
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn site_pages_have_unique_names() {
+    for engine in common::engines() {
+        let pages = clap_show::ClapShow::new(&command()).engine(engine).render_site().unwrap();
+        let names = pages.iter().map(|p| p.name.as_str()).collect::<Vec<&str>>();
+
+        assert_eq!(
+            names,
+            [
+                "index.html",
+                "index-2.html",
+                "index-a-b-c.html",
+                "index-a-b.html",
+                "index-a.html",
+                "index-a-b-2.html",
+                "index-search.html",
+                "index-search-index.html",
+                "search-index.js",
+            ],
+            "{:?}",
+            engine
+        );
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+    }
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn site_links_point_to_pages() {
+    for engine in common::engines() {
+        let pages = clap_show::ClapShow::new(&command()).engine(engine).render_site().unwrap();
+        let names = pages.iter().map(|p| p.name.as_str()).collect::<HashSet<&str>>();
+
+        for page in &pages {
+            for href in links(&page.content) {
+                assert!(names.contains(href), "{:?}: {} links to {}", engine, page.name, href);
+            }
+        }
+
+        let search = pages.iter().find(|p| p.name == "search-index.js").unwrap();
+        assert!(search.content.contains("\"href\":\"index-a-b-2.html#index-a-b-2\""));
+    }
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn site_is_written() {
+    let dir = std::env::temp_dir().join(format!("clap-show-site-{}", std::process::id()));
+
+    let paths = clap_show::write_site(&command(), &dir).unwrap();
+    let written = std::fs::read_dir(&dir).unwrap().count();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(written, paths.len());
+}
+
+#[test]
+fn man_pages_have_unique_names() {
+    let pages = clap_show::render_man_pages(&command()).unwrap();
+    let names = pages.iter().map(|p| p.name.as_str()).collect::<Vec<&str>>();
+
+    assert_eq!(
+        names,
+        [
+            "index.1",
+            "index-a-b-c.1",
+            "index-a-b.1",
+            "index-a.1",
+            "index-a-b-2.1",
+            "index-search.1",
+            "index-search-index.1",
+        ]
+    );
+
+    let a = &pages[3].content;
+    assert!(a.contains(".SH \"SEE ALSO\"\n\\fBindex\\fR(1), \\fBindex\\-a\\-b\\-2\\fR(1)\n"));
+}
+
+#[test]
+fn man_pages_are_written() {
+    let dir = std::env::temp_dir().join(format!("clap-show-man-{}", std::process::id()));
+
+    let paths = clap_show::write_man_pages(&command(), &dir).unwrap();
+    let written = std::fs::read_dir(&dir).unwrap().count();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(written, paths.len());
+}
@@ -0,0 +1,121 @@
+//! Signatures of the arguments match the ones in `--help`, and the number of
+//! values each argument takes is documented.
+
+use clap::{Arg, ArgAction, Command};
+use clap_show::DocArg;
+use pretty_assertions::assert_eq;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .arg(Arg::new("opt").long("opt").num_args(2).value_names(["A", "B"]))
+        .arg(Arg::new("pair").short('p').long("pair").value_name("A").require_equals(true))
+        .arg(
+            Arg::new("color")
+                .long("color")
+                .value_name("WHEN")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("always"),
+        )
+        .arg(Arg::new("list").long("list").num_args(1..).value_delimiter(','))
+        .arg(Arg::new("fast").long("fast").action(ArgAction::SetTrue))
+        .arg(Arg::new("FILE").required(true).num_args(1..))
+        .arg(Arg::new("ARGS").num_args(1..).last(true))
+}
+
+/// Arguments of the main command, positional ones first like in `--help`.
+fn args() -> Vec<DocArg> {
+    let page = clap_show::extract(&command());
+    [page.main.arguments, page.main.options].concat()
+}
+
+/// Signatures listed by `--help`, without their help.
+fn help_flags() -> Vec<String> {
+    let help = command().render_help().to_string();
+    help.lines()
+        .filter(|t| t.starts_with("  "))
+        .map(|t| t.trim().split("  ").next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn flags_match_help() {
+    let mut flags: Vec<String> = args().into_iter().map(|t| t.flags).collect();
+
+    // `--help` shows arguments after `--` as `[ARGS]...`, the documentation
+    // uses the usage form instead, which tells how to give them
+    let last = flags.remove(1);
+    assert_eq!(last, "[-- <ARGS>...]");
+    assert!(command().render_usage().to_string().contains(&last));
+
+    let mut help = help_flags();
+    assert_eq!(help.remove(1), "[ARGS]...");
+    assert_eq!(
+        help,
+        ["<FILE>...", "--opt <A> <B>", "-p, --pair=<A>", "--color[=<WHEN>]", "--list <list>...", "--fast", "-h, --help"]
+    );
+    assert_eq!(flags, help);
+}
+
+#[test]
+fn num_values() {
+    let values: Vec<(String, usize, Option<usize>)> =
+        args().into_iter().map(|t| (t.flags, t.min_values, t.max_values)).collect();
+    let values: Vec<(&str, usize, Option<usize>)> = values.iter().map(|t| (t.0.as_str(), t.1, t.2)).collect();
+
+    assert_eq!(
+        values,
+        [
+            ("<FILE>...", 1, None),
+            ("[-- <ARGS>...]", 1, None),
+            ("--opt <A> <B>", 2, Some(2)),
+            ("-p, --pair=<A>", 1, Some(1)),
+            ("--color[=<WHEN>]", 0, Some(1)),
+            ("--list <list>...", 1, None),
+            ("--fast", 0, Some(0)),
+            ("-h, --help", 0, Some(0)),
+        ]
+    );
+}
+
+#[test]
+fn value_counts() {
+    let counts: Vec<(String, String)> = args().into_iter().map(|t| (t.flags, t.value_count)).collect();
+    let counts: Vec<(&str, &str)> = counts.iter().map(|t| (t.0.as_str(), t.1.as_str())).collect();
+
+    assert_eq!(
+        counts,
+        [
+            ("<FILE>...", "Takes at least 1 value"),
+            ("[-- <ARGS>...]", "Takes at least 1 value"),
+            ("--opt <A> <B>", "Takes 2 values"),
+            ("-p, --pair=<A>", ""),
+            ("--color[=<WHEN>]", "Takes at most 1 value"),
+            ("--list <list>...", "Takes at least 1 value"),
+            ("--fast", ""),
+            ("-h, --help", ""),
+        ]
+    );
+}
+
+/// An argument taking a bounded range of values.
+fn ranged() -> Command {
+    Command::new("mycli").arg(Arg::new("FILE").num_args(2..=3))
+}
+
+#[test]
+fn value_counts_in_every_backend() {
+    let markdown = clap_show::render_markdown(&ranged()).unwrap();
+    assert!(markdown.contains("| `[FILE] [FILE]...` | Takes 2 to 3 values |"), "{}", markdown);
+
+    let man = &clap_show::render_man_pages(&ranged()).unwrap()[0].content;
+    assert!(man.contains("[FILE] [FILE]...\nTakes 2 to 3 values.\n"), "{}", man);
+
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    for engine in common::engines() {
+        let html = clap_show::ClapShow::new(&ranged()).engine(engine).render().unwrap();
+        assert!(html.contains("<div class=\"arg-details\">Takes 2 to 3 values</div>"), "{:?}", engine);
+    }
+}
@@ -0,0 +1,97 @@
+//! Global arguments are documented once, where they are defined, and listed
+//! as inherited by every subcommand below.
+
+use clap::{Arg, Command};
+use clap_show::{ClapShow, DocCommand};
+use pretty_assertions::assert_eq;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .version("1.0.0")
+        .arg(Arg::new("verbose").long("verbose").global(true).num_args(0))
+        .subcommand(
+            Command::new("deploy")
+                .arg(Arg::new("region").long("region").global(true))
+                .subcommand(Command::new("now").arg(Arg::new("force").long("force").num_args(0))),
+        )
+}
+
+fn options(command: &DocCommand) -> Vec<&str> {
+    command.options.iter().map(|t| t.flags.as_str()).collect()
+}
+
+fn inherited(command: &DocCommand) -> Vec<(&str, &str, &str)> {
+    command
+        .inherited
+        .iter()
+        .map(|t| (t.arg.flags.as_str(), t.cmd_chain.as_str(), t.anchor.as_str()))
+        .collect()
+}
+
+#[test]
+fn globals_are_documented_where_defined() {
+    let page = clap_show::extract(&command());
+    let deploy = &page.subcommands[0];
+
+    assert_eq!(options(&page.main), ["--verbose", "-h, --help", "-V, --version"]);
+    assert!(page.main.inherited.is_empty());
+
+    assert_eq!(deploy.cmd_chain, "mycli deploy");
+    assert_eq!(options(deploy), ["--region <region>", "-h, --help"]);
+    assert_eq!(inherited(deploy), [("--verbose", "mycli", "mycli")]);
+}
+
+#[test]
+fn deeper_levels_inherit_every_global_once() {
+    let page = clap_show::extract(&command());
+    let now = page.subcommands.iter().find(|t| t.cmd_chain == "mycli deploy now").unwrap();
+
+    assert_eq!(options(now), ["--force", "-h, --help"]);
+    // In the order of `--help`, which starts with the closest parent
+    assert_eq!(
+        inherited(now),
+        [
+            ("--region <region>", "mycli deploy", "mycli-deploy"),
+            ("--verbose", "mycli", "mycli"),
+        ]
+    );
+}
+
+#[test]
+fn help_flags_can_be_left_out() {
+    let command = command();
+    let json = ClapShow::new(&command).help_flags(false).render_json().unwrap();
+    let page: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    let all = std::iter::once(&page["main"])
+        .chain(page["subcommands"].as_array().unwrap())
+        .collect::<Vec<_>>();
+    for command in &all {
+        let flags = command["options"].as_array().unwrap().iter().map(|t| t["flags"].as_str().unwrap());
+        let names = command["commands"].as_array().unwrap().iter().map(|t| &t["name"]);
+        assert!(!flags.clone().any(|t| t.contains("--help") || t.contains("--version")));
+        assert!(!names.clone().any(|t| t == "help"), "{}", command["cmd_chain"]);
+    }
+
+    let chains = all.iter().map(|t| t["cmd_chain"].as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(chains, ["mycli", "mycli deploy", "mycli deploy now"]);
+    assert_eq!(page["tree"]["children"].as_array().unwrap().len(), 1);
+
+    // The `help` subcommand is documented by default
+    let page = clap_show::extract(&command);
+    assert!(page.subcommands.iter().any(|t| t.cmd_chain == "mycli help"));
+    assert!(page.main.commands.iter().any(|t| t.name == "help"));
+}
+
+#[test]
+fn backends_link_to_the_defining_command() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+    assert!(markdown.contains("| `--region <region>` | Defined in: [mycli deploy](#mycli-deploy) |"));
+
+    let pages = clap_show::render_man_pages(&command()).unwrap();
+    let now = pages.iter().find(|p| p.name == "mycli-deploy-now.1").unwrap();
+    assert!(now.content.contains(
+        "\\fB\\-\\-region\\fR <region>\n[defined in: \\fBmycli\\-deploy\\fR(1)]\n"
+    ));
+    assert!(now.content.contains("\\fB\\-\\-verbose\\fR\n[defined in: \\fBmycli\\fR(1)]\n"));
+}
@@ -0,0 +1,82 @@
+//! Version, author and the texts before and after the help are documented.
+
+use clap::Command;
+use clap_show::{ClapShow, Verbosity};
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .version("1.2.0")
+        .long_version("1.2.0\ncommit abc123")
+        .author("Jane Doe")
+        .before_help("Before the help")
+        .after_help("Run `mycli deploy` to deploy")
+        .after_long_help("Examples:\n\n- mycli deploy\n- mycli deploy --force")
+        .propagate_version(true)
+        .subcommand(Command::new("deploy").after_help("Deploys take a while"))
+}
+
+#[test]
+fn extracts_header_and_footer() {
+    let page = clap_show::extract(&command());
+
+    assert_eq!(page.main.version.as_deref(), Some("1.2.0\ncommit abc123"));
+    assert_eq!(page.main.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(page.main.before_help, "Before the help");
+    assert!(page.main.after_help.starts_with("Examples:"));
+    assert_eq!(page.subcommands[0].version, page.main.version);
+    assert_eq!(page.subcommands[0].author, None);
+    assert_eq!(page.subcommands[0].after_help, "Deploys take a while");
+}
+
+#[test]
+fn summary_prefers_short_texts() {
+    let markdown = ClapShow::new(&command())
+        .verbosity(Verbosity::Summary)
+        .render_markdown()
+        .unwrap();
+
+    assert!(markdown.contains("Version: 1.2.0  \nAuthor: Jane Doe\n"));
+    assert!(markdown.contains("### Notes\n\nRun \\`mycli deploy\\` to deploy\n"));
+    assert!(!markdown.contains("commit abc123"));
+}
+
+#[test]
+fn markdown_has_header_and_notes() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+
+    assert!(markdown.starts_with("# mycli\n\nVersion: 1.2.0 commit abc123  \nAuthor: Jane Doe\n\nBefore the help\n"));
+    assert!(markdown.contains("### Notes\n\nExamples:\n\n- mycli deploy\n"));
+    assert!(markdown.contains("### Notes\n\nDeploys take a while\n"));
+}
+
+#[test]
+fn man_pages_have_version_notes_and_authors() {
+    let pages = ClapShow::new(&command()).render_man_pages().unwrap();
+
+    let main = &pages[0].content;
+    assert!(main.starts_with(".TH \"MYCLI\" 1 \"\" \"mycli 1.2.0\"\n"));
+    assert!(main.contains(".SH DESCRIPTION\nBefore the help\n"));
+    assert!(main.contains(".SH NOTES\nExamples:\n"));
+    assert!(main.contains(".SH AUTHORS\nJane Doe\n"));
+
+    let deploy = &pages[1].content;
+    assert!(deploy.starts_with(".TH \"MYCLI\\-DEPLOY\" 1 \"\" \"mycli deploy 1.2.0\"\n"));
+    assert!(deploy.contains(".SH NOTES\nDeploys take a while\n"));
+    assert!(!deploy.contains(".SH AUTHORS"));
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn html_has_header_and_notes() {
+    for engine in common::engines() {
+        let html = ClapShow::new(&command()).engine(engine).render().unwrap();
+
+        assert!(html.contains("<div class=\"command-meta\">Author: Jane Doe</div>"), "{:?}", engine);
+        assert!(html.contains("commit abc123"), "{:?}", engine);
+        assert!(html.contains("<div class=\"description\">Before the help</div>"), "{:?}", engine);
+        assert!(html.contains("<li>mycli deploy --force</li>"), "{:?}", engine);
+        assert!(html.contains("<div class=\"description\">Deploys take a while</div>"), "{:?}", engine);
+    }
+}
@@ -0,0 +1,47 @@
+//! Paragraphs, lists, code blocks and links of help texts in every backend.
+
+use clap::Command;
+use clap_show::ClapShow;
+
+fn command() -> Command {
+    Command::new("mycli").long_about(
+        "Deploy the application to\nthe cloud, see https://example.com/docs.\n\n\
+         Targets:\n- staging, for\n  previews\n* production\n\n\
+         Example:\n\n    mycli deploy\n      --fast",
+    )
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn html() {
+    let html = ClapShow::new(&command()).render().unwrap();
+
+    assert!(html.contains(
+        "<p>Deploy the application to the cloud, see \
+         <a href=\"https://example.com/docs\">https://example.com/docs</a>.</p>"
+    ));
+    assert!(html.contains("<ul><li>staging, for previews</li><li>production</li></ul>"));
+    assert!(html.contains("<pre><code>mycli deploy\n  --fast</code></pre>"));
+}
+
+#[test]
+fn markdown() {
+    let markdown = ClapShow::new(&command()).render_markdown().unwrap();
+
+    assert!(markdown.contains(
+        "Deploy the application to the cloud, see <https://example.com/docs>.\n\n\
+         Targets:\n\n- staging, for previews\n- production\n\n\
+         Example:\n\n```text\nmycli deploy\n  --fast\n```\n"
+    ));
+}
+
+#[test]
+fn man() {
+    let pages = ClapShow::new(&command()).render_man_pages().unwrap();
+
+    assert!(pages[0].content.contains(
+        "Deploy the application to the cloud, see https://example.com/docs.\n\
+         .PP\nTargets:\n.RS\n.IP \\(bu 2\nstaging, for previews\n.IP \\(bu 2\nproduction\n.RE\n\
+         .PP\nExample:\n.RS\n.nf\nmycli deploy\n  \\-\\-fast\n.fi\n.RE\n"
+    ));
+}
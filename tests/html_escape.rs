@@ -0,0 +1,106 @@
+//! Help texts are escaped in every HTML output, unless they are trusted.
+
+#![cfg(any(feature = "handlebars", feature = "ramhorns"))]
+
+use clap::{Arg, Command};
+use clap_show::ClapShow;
+use common::{engines, outputs};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .about("Parse a Vec<T> & friends")
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .value_name("FILE")
+                .help("Read <FILE> or <stdin>"),
+        )
+        .arg(
+            Arg::new("evil")
+                .long("evil")
+                .help("Runs <script>alert(\"pwned\")</script>"),
+        )
+        .subcommand(Command::new("deploy").about("Deploy a Result<(), E>\nto the cloud"))
+}
+
+#[test]
+fn escapes_generic_types() {
+    let command = command();
+    for engine in engines() {
+        let html = ClapShow::new(&command).engine(engine).render().unwrap();
+
+        assert!(html.contains("Parse a Vec&lt;T&gt; &amp; friends"), "{:?}", engine);
+        assert!(html.contains("Deploy a Result&lt;(), E&gt; to the cloud"), "{:?}", engine);
+        assert!(!html.contains("Vec<T>"), "{:?}", engine);
+    }
+}
+
+#[test]
+fn escapes_placeholders() {
+    let command = command();
+    for engine in engines() {
+        let html = ClapShow::new(&command).engine(engine).render().unwrap();
+
+        assert!(html.contains("Read &lt;FILE&gt; or &lt;stdin&gt;"), "{:?}", engine);
+        assert!(html.contains("--input &lt;FILE&gt;"), "{:?}", engine);
+        assert!(!html.contains("<FILE>"), "{:?}", engine);
+    }
+}
+
+#[test]
+fn escapes_script_tags() {
+    let command = command();
+    for engine in engines() {
+        for html in outputs(ClapShow::new(&command).engine(engine)) {
+            // The pages have scripts of their own, but none from the help texts
+            assert!(!html.contains("<script>alert"), "{:?}", engine);
+        }
+        let site = ClapShow::new(&command).engine(engine).render_site().unwrap();
+        for page in site {
+            assert!(!page.content.contains("<script>alert"), "{:?}: {}", engine, page.name);
+        }
+
+        let html = ClapShow::new(&command).engine(engine).render().unwrap();
+        assert!(
+            html.contains("Runs &lt;script&gt;alert(&quot;pwned&quot;)&lt;/script&gt;"),
+            "{:?}",
+            engine
+        );
+    }
+}
+
+#[test]
+fn trusted_html_is_written_as_is() {
+    let command = command();
+    for engine in engines() {
+        let html = ClapShow::new(&command)
+            .engine(engine)
+            .trusted_html(true)
+            .render()
+            .unwrap();
+
+        assert!(html.contains("Runs <script>alert(\"pwned\")</script>"), "{:?}", engine);
+        assert!(html.contains("Deploy a Result<(), E> to the cloud"), "{:?}", engine);
+    }
+}
+
+#[test]
+fn escaping_matches_between_engines() {
+    let command = command();
+    let descriptions = engines()
+        .into_iter()
+        .map(|engine| {
+            let html = ClapShow::new(&command).engine(engine).render().unwrap();
+            let start = html.find("Runs ").unwrap();
+            let end = start + html[start..].find('<').unwrap();
+            html[start..end].trim().to_string()
+        })
+        .collect::<Vec<_>>();
+
+    for description in &descriptions {
+        assert_eq!(description, &descriptions[0]);
+    }
+}
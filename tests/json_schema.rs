@@ -0,0 +1,147 @@
+//! The output of `render_json` follows the shipped `JSON_SCHEMA`.
+//!
+//! Every object is checked against its definition, so changing the model
+//! without changing the schema fails here.
+
+use std::collections::HashSet;
+
+use clap::builder::PossibleValue;
+use clap::{Arg, ArgGroup, Command};
+use serde_json::{Map, Value};
+
+/// A command using every part of the model.
+fn command() -> Command {
+    Command::new("mycli")
+        .version("1.0.0")
+        .author("Jane Doe")
+        .about("Does things")
+        .before_help("Read this first")
+        .after_help("Examples go here")
+        .arg(Arg::new("verbose").long("verbose").short('v').num_args(0).global(true))
+        .arg(Arg::new("file").value_name("FILE").help("Input file"))
+        .arg(Arg::new("stdin").long("stdin").num_args(0))
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .visible_alias("fmt")
+                .env("MYCLI_FORMAT")
+                .default_value("json")
+                .value_parser([PossibleValue::new("json").help("As JSON"), PossibleValue::new("yaml")])
+                .conflicts_with("stdin"),
+        )
+        .arg(Arg::new("tags").long("tags").value_delimiter(',').num_args(1..))
+        .arg(Arg::new("color").long("color").help_heading("Display"))
+        .group(ArgGroup::new("source").args(["file", "stdin"]).required(true))
+        .subcommand_help_heading("Actions")
+        .subcommand(
+            Command::new("sync")
+                .short_flag('S')
+                .long_flag("sync")
+                .visible_alias("sy")
+                .long_about("Synchronize the packages")
+                .subcommand(Command::new("all").arg(Arg::new("force").long("force").num_args(0))),
+        )
+}
+
+/// Check `value` against the `schema` node, recording the definitions used.
+fn check(value: &Value, schema: &Value, root: &Value, path: &str, used: &mut HashSet<String>) {
+    let schema = schema.as_object().unwrap();
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.strip_prefix("#/$defs/").unwrap();
+        used.insert(name.to_string());
+        let definition = root["$defs"]
+            .get(name)
+            .unwrap_or_else(|| panic!("{}: no definition {}", path, name));
+        return check(value, definition, root, path, used);
+    }
+
+    if let Some(expected) = schema.get("const") {
+        assert_eq!(value, expected, "{}", path);
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        assert!(allowed.contains(value), "{}: {} isn't in {:?}", path, value, allowed);
+    }
+    if let Some(types) = schema.get("type") {
+        let types = match types {
+            Value::Array(types) => types.iter().map(|t| t.as_str().unwrap()).collect(),
+            types => vec![types.as_str().unwrap()],
+        };
+        assert!(
+            types.iter().any(|t| is_type(value, t)),
+            "{}: {} isn't {:?}",
+            path,
+            value,
+            types
+        );
+    }
+
+    match value {
+        Value::Array(items) => {
+            if let Some(schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item, schema, root, &format!("{}[{}]", path, i), used);
+                }
+            }
+        }
+        Value::Object(object) => check_object(object, schema, root, path, used),
+        _ => {}
+    }
+}
+
+fn check_object(
+    object: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    root: &Value,
+    path: &str,
+    used: &mut HashSet<String>,
+) {
+    let properties = schema["properties"].as_object().unwrap();
+    for key in schema["required"].as_array().unwrap() {
+        let key = key.as_str().unwrap();
+        assert!(object.contains_key(key), "{}: missing {}", path, key);
+    }
+    for (key, value) in object {
+        let property = properties
+            .get(key)
+            .unwrap_or_else(|| panic!("{}: {} isn't in the schema", path, key));
+        check(value, property, root, &format!("{}.{}", path, key), used);
+    }
+}
+
+fn is_type(value: &Value, name: &str) -> bool {
+    match name {
+        "string" => value.is_string(),
+        "integer" => value.is_u64() || value.is_i64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => panic!("unknown type {}", name),
+    }
+}
+
+#[test]
+fn output_follows_the_schema() {
+    let schema: Value = serde_json::from_str(clap_show::JSON_SCHEMA).unwrap();
+    let json: Value = serde_json::from_str(&clap_show::render_json(&command()).unwrap()).unwrap();
+
+    let mut used = HashSet::new();
+    check(&json, &schema, &schema, "$", &mut used);
+
+    // Every definition was checked against some output
+    for name in schema["$defs"].as_object().unwrap().keys() {
+        assert!(used.contains(name), "no output checked against {}", name);
+    }
+}
+
+#[test]
+fn schema_version_matches() {
+    let schema: Value = serde_json::from_str(clap_show::JSON_SCHEMA).unwrap();
+    let json: Value = serde_json::from_str(&clap_show::render_json(&command()).unwrap()).unwrap();
+
+    assert_eq!(
+        schema["properties"]["schema_version"]["const"],
+        clap_show::SCHEMA_VERSION
+    );
+    assert_eq!(json["schema_version"], clap_show::SCHEMA_VERSION);
+}
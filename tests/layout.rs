@@ -0,0 +1,146 @@
+//! The HTML pages are well formed and use semantic, accessible markup.
+
+#![cfg(any(feature = "handlebars", feature = "ramhorns"))]
+
+use std::collections::HashSet;
+
+use clap::{Arg, ArgGroup, Command};
+use clap_show::{ClapShow, Engine};
+use common::engines;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .about("Manage things")
+        .after_help("Run `mycli deploy` first")
+        .arg(Arg::new("verbose").long("verbose").global(true).num_args(0))
+        .arg(Arg::new("input").help("Input file\n\n- first\n- second"))
+        .arg(Arg::new("json").long("json").num_args(0))
+        .arg(Arg::new("yaml").long("yaml").num_args(0))
+        .group(ArgGroup::new("format").args(["json", "yaml"]))
+        .subcommand(
+            Command::new("deploy")
+                .about("Deploy things")
+                .after_help("Deploys are logged")
+                .arg(Arg::new("force").long("force").num_args(0).help_heading("Danger"))
+                .subcommand(Command::new("now")),
+        )
+}
+
+/// Every HTML output of `engine`: the single page and each site page.
+fn outputs(engine: Engine) -> Vec<String> {
+    common::outputs(ClapShow::new(&command()).engine(engine))
+}
+
+/// Start and end tags of `html`, as `(name, is_end)`, skipping scripts and styles.
+fn tags(html: &str) -> Vec<(String, bool)> {
+    let mut tags = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let end = rest.find('>').unwrap();
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('!') {
+            continue;
+        }
+
+        let is_end = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap()
+            .to_lowercase();
+        if !is_end && (name == "script" || name == "style") {
+            let close = format!("</{}>", name);
+            rest = &rest[rest.find(&close).unwrap() + close.len()..];
+            continue;
+        }
+        tags.push((name, is_end));
+    }
+    tags
+}
+
+#[test]
+fn tags_are_balanced() {
+    const VOID: &[&str] = &["meta", "link", "br", "hr", "img", "input", "wbr"];
+
+    for engine in engines() {
+        for html in outputs(engine) {
+            let mut open: Vec<String> = Vec::new();
+            for (name, is_end) in tags(&html) {
+                if VOID.contains(&name.as_str()) {
+                    assert!(!is_end, "{:?}: </{}>", engine, name);
+                } else if is_end {
+                    assert_eq!(open.pop().as_deref(), Some(name.as_str()), "{:?}", engine);
+                } else {
+                    open.push(name);
+                }
+            }
+            assert!(open.is_empty(), "{:?}: unclosed {:?}", engine, open);
+        }
+    }
+}
+
+#[test]
+fn ids_are_unique() {
+    for engine in engines() {
+        for html in outputs(engine) {
+            let ids = html.split(" id=\"").skip(1).map(|s| &s[..s.find('"').unwrap()]);
+
+            let mut seen = HashSet::new();
+            for id in ids {
+                assert!(seen.insert(id), "{:?}: duplicate id {}", engine, id);
+            }
+        }
+    }
+}
+
+#[test]
+fn uses_landmarks() {
+    for engine in engines() {
+        for html in outputs(engine) {
+            assert!(html.starts_with("<!DOCTYPE html>"), "{:?}", engine);
+            assert!(html.contains("<html lang=\"en\">"), "{:?}", engine);
+            assert!(html.contains("name=\"viewport\""), "{:?}", engine);
+            assert!(html.contains("<a class=\"skip-link\" href=\"#content\">"), "{:?}", engine);
+            assert!(html.contains("<main class=") && html.contains("id=\"content\""), "{:?}", engine);
+            assert!(html.contains("<nav class="), "{:?}", engine);
+        }
+
+        let html = ClapShow::new(&command()).engine(engine).render().unwrap();
+        assert!(html.contains("aria-controls=\"menu-content\""), "{:?}", engine);
+        assert!(html.contains("<dt id=\"mycli-deploy--force\"><code>--force</code>"), "{:?}", engine);
+        assert!(!html.contains("class=\"table\""), "{:?}", engine);
+    }
+}
+
+#[test]
+fn headings_never_skip_a_level() {
+    for engine in engines() {
+        for html in outputs(engine) {
+            let levels = tags(&html)
+                .into_iter()
+                .filter(|(name, is_end)| !is_end && name.len() == 2 && name.starts_with('h'))
+                .map(|(name, _)| name[1..].parse::<usize>().unwrap())
+                .collect::<Vec<_>>();
+
+            assert_eq!(levels.first(), Some(&1), "{:?}", engine);
+            for pair in levels.windows(2) {
+                assert!(pair[1] <= pair[0] + 1, "{:?}: {:?}", engine, levels);
+            }
+        }
+    }
+}
+
+#[test]
+fn site_pages_link_to_their_title() {
+    for engine in engines() {
+        let site = ClapShow::new(&command()).engine(engine).render_site().unwrap();
+        let deploy = site.iter().find(|t| t.name == "mycli-deploy.html").unwrap();
+
+        assert!(deploy.content.contains("<h1 id=\"mycli-deploy\">mycli deploy</h1>"), "{:?}", engine);
+    }
+}
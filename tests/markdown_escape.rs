@@ -0,0 +1,58 @@
+//! Help texts and values are escaped in the Markdown output.
+
+use clap::{Arg, Command};
+use clap_show::ClapShow;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .about("Deploy <T> & stuff")
+        .arg(
+            Arg::new("pattern")
+                .long("pattern")
+                .help("Match *.rs files, not __init__ or [links]")
+                .default_value("**/*_test`s`"),
+        )
+        .arg(
+            Arg::new("mode")
+                .long("mode")
+                .help("# Not a heading")
+                .value_parser(["a|b", "`tick"]),
+        )
+        .subcommand(Command::new("deploy").about("+ not a list\n\n1. not an item"))
+}
+
+fn markdown() -> String {
+    ClapShow::new(&command()).render_markdown().unwrap()
+}
+
+#[test]
+fn escapes_html() {
+    let markdown = markdown();
+
+    assert!(markdown.contains("Deploy \\<T\\> \\& stuff\n"));
+    assert!(!markdown.contains("<T>"));
+}
+
+#[test]
+fn escapes_emphasis_and_links() {
+    let markdown = markdown();
+
+    assert!(markdown.contains("Match \\*.rs files, not \\_\\_init\\_\\_ or \\[links\\]"));
+}
+
+#[test]
+fn escapes_block_markers() {
+    let markdown = markdown();
+
+    assert!(markdown.contains("| \\# Not a heading<br>"));
+    assert!(markdown.contains("\n\\+ not a list\n\n1\\. not an item\n"));
+}
+
+#[test]
+fn code_spans_hold_backticks_and_pipes() {
+    let markdown = markdown();
+
+    assert!(markdown.contains("Default: `` **/*_test`s` ``"));
+    assert!(markdown.contains("- `a\\|b`"));
+    assert!(markdown.contains("- `` `tick ``"));
+}
@@ -0,0 +1,144 @@
+//! Conflicts and groups of arguments are documented in every backend.
+
+use clap::{Arg, ArgGroup, Command};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .arg(Arg::new("config").long("config"))
+        .arg(Arg::new("profile").long("profile"))
+        .arg(Arg::new("stdin").long("stdin").num_args(0))
+        .arg(Arg::new("file").value_name("FILE"))
+        .arg(Arg::new("json").long("json").num_args(0).conflicts_with("yaml"))
+        .arg(Arg::new("yaml").long("yaml").num_args(0))
+        .arg(Arg::new("dry-run").long("dry-run").num_args(0).conflicts_with("settings"))
+        .group(ArgGroup::new("format").args(["json", "yaml"]))
+        .group(ArgGroup::new("source").args(["stdin", "file"]).required(true))
+        .group(
+            ArgGroup::new("settings")
+                .args(["config", "profile"])
+                .required(true)
+                .multiple(true),
+        )
+}
+
+fn arg<'a>(page: &'a clap_show::DocPage, anchor: &str) -> &'a clap_show::DocArg {
+    page.main
+        .options
+        .iter()
+        .chain(&page.main.arguments)
+        .find(|t| t.anchor == anchor)
+        .unwrap()
+}
+
+#[test]
+fn extracts_relationships() {
+    let page = clap_show::extract(&command());
+
+    assert_eq!(arg(&page, "mycli--json").conflicts_with, ["--yaml"]);
+    assert_eq!(arg(&page, "mycli--yaml").conflicts_with, ["--json"]);
+    assert_eq!(arg(&page, "mycli--dry-run").conflicts_with, ["--config", "--profile"]);
+    assert_eq!(arg(&page, "mycli--config").conflicts_with, ["--dry-run"]);
+    assert!(arg(&page, "mycli--stdin").conflicts_with.is_empty());
+}
+
+#[test]
+fn extracts_groups() {
+    let page = clap_show::extract(&command());
+    let groups = page
+        .main
+        .groups
+        .iter()
+        .map(|t| (t.name.as_str(), t.args.join(" "), t.rule.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        groups,
+        [
+            ("format", "--json --yaml".to_string(), "At most one of these can be used"),
+            ("source", "--stdin <FILE>".to_string(), "Exactly one of these is required"),
+            ("settings", "--config --profile".to_string(), "At least one of these is required"),
+        ]
+    );
+}
+
+#[test]
+fn markdown_shows_relationships() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+
+    assert!(markdown.contains("| `--dry-run` | Conflicts with: `--config`, `--profile` |"));
+    assert!(markdown.contains("| `--json` | Conflicts with: `--yaml` |"));
+    assert!(markdown.contains("| `format` | At most one of these can be used: `--json`, `--yaml` |"));
+    assert!(markdown.contains("| `source` | Exactly one of these is required: `--stdin`, `<FILE>` |"));
+    assert!(markdown
+        .contains("| `settings` | At least one of these is required: `--config`, `--profile` |"));
+}
+
+#[test]
+fn man_pages_show_relationships() {
+    let pages = clap_show::render_man_pages(&command()).unwrap();
+    let man = &pages[0].content;
+
+    assert!(man.contains("\\fB\\-\\-dry\\-run\\fR\n[conflicts with: \\fB\\-\\-config\\fR, \\fB\\-\\-profile\\fR]\n"));
+    assert!(man.contains("\\fB\\-\\-json\\fR\n[conflicts with: \\fB\\-\\-yaml\\fR]\n"));
+    assert!(man.contains(
+        ".SH GROUPS\n.TP\n\\fBformat\\fR\nAt most one of these can be used: \\fB\\-\\-json\\fR, \\fB\\-\\-yaml\\fR.\n"
+    ));
+    assert!(man.contains("Exactly one of these is required: \\fB\\-\\-stdin\\fR, \\fB<FILE>\\fR.\n"));
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn html_shows_relationships() {
+    for engine in common::engines() {
+        let html = clap_show::ClapShow::new(&command()).engine(engine).render().unwrap();
+        let html = html.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+        assert!(
+            html.contains("Conflicts with: <code>--config</code> <code>--profile</code>"),
+            "{:?}",
+            engine
+        );
+        assert!(html.contains("Conflicts with: <code>--yaml</code>"), "{:?}", engine);
+        assert!(
+            html.contains("At most one of these can be used: <code>--json</code> <code>--yaml</code>"),
+            "{:?}",
+            engine
+        );
+        assert!(
+            html.contains("Exactly one of these is required: <code>--stdin</code> <code>&lt;FILE&gt;</code>"),
+            "{:?}",
+            engine
+        );
+    }
+}
+
+/// A global argument conflicting with a group, which clap's own lookup of
+/// conflicts panics on.
+fn global_conflict() -> Command {
+    Command::new("mycli")
+        .arg(Arg::new("quiet").long("quiet").num_args(0).global(true).conflicts_with("format"))
+        .arg(Arg::new("json").long("json").num_args(0))
+        .arg(Arg::new("yaml").long("yaml").num_args(0))
+        .group(ArgGroup::new("format").args(["json", "yaml"]))
+}
+
+#[test]
+fn global_argument_conflicting_with_a_group() {
+    let page = clap_show::extract(&global_conflict());
+
+    assert_eq!(arg(&page, "mycli--quiet").conflicts_with, ["--json", "--yaml"]);
+    assert_eq!(arg(&page, "mycli--json").conflicts_with, ["--quiet"]);
+
+    assert!(clap_show::render_markdown(&global_conflict()).is_ok());
+    assert!(clap_show::render_man_pages(&global_conflict()).is_ok());
+    assert!(clap_show::render_json(&global_conflict()).is_ok());
+    #[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+    for engine in common::engines() {
+        let command = global_conflict();
+        let builder = clap_show::ClapShow::new(&command).engine(engine);
+        assert!(builder.render().is_ok() && builder.render_site().is_ok(), "{:?}", engine);
+    }
+}
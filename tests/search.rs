@@ -0,0 +1,48 @@
+//! The HTML pages embed a search index, or load it from beside them.
+
+#![cfg(any(feature = "handlebars", feature = "ramhorns"))]
+
+use clap::{Arg, Command};
+use clap_show::ClapShow;
+
+fn command() -> Command {
+    Command::new("mycli").subcommand(
+        Command::new("deploy").arg(
+            Arg::new("region")
+                .long("region")
+                .env("MYCLI_REGION")
+                .value_parser(["eu-west", "us-east"])
+                .help("Deploy to </script><script>alert(1)</script>"),
+        ),
+    )
+}
+
+#[test]
+fn single_page_embeds_the_index() {
+    let html = ClapShow::new(&command()).render().unwrap();
+
+    let start = html.find("<script type=\"application/json\" id=\"search-index\">").unwrap();
+    let end = start + html[start..].find("</script>").unwrap();
+    let index = &html[start..end];
+
+    assert!(index.contains("\"href\":\"#mycli-deploy--region\""));
+    assert!(index.contains("mycli_region"));
+    assert!(index.contains("us-east"));
+    assert!(index.contains("\\u003cscript>alert(1)"));
+}
+
+#[test]
+fn site_loads_the_index_beside_the_pages() {
+    let pages = ClapShow::new(&command()).render_site().unwrap();
+
+    let index = pages.iter().find(|p| p.name == "search-index.js").unwrap();
+    assert!(index.content.starts_with("window.clapShowSearch = "));
+    assert!(index
+        .content
+        .contains("\"href\":\"mycli-deploy.html#mycli-deploy--region\""));
+
+    for page in pages.iter().filter(|p| p.name.ends_with(".html")) {
+        assert!(page.content.contains("<script src=\"search-index.js\"></script>"), "{}", page.name);
+        assert!(page.content.contains("id=\"search-input\""), "{}", page.name);
+    }
+}
@@ -0,0 +1,81 @@
+//! Arguments with a help heading are listed under it, like in `--help`.
+
+use clap::{Arg, Command};
+use pretty_assertions::assert_eq;
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .subcommand_help_heading("Tasks")
+        .arg(Arg::new("input"))
+        .arg(Arg::new("output").long("output").help_heading("Output"))
+        .arg(Arg::new("verbose").long("verbose").num_args(0))
+        .arg(Arg::new("dry-run").long("dry-run").num_args(0).help_heading("Safety"))
+        .arg(Arg::new("template").help_heading("Output"))
+        .arg(Arg::new("color").long("color").help_heading("Output"))
+        .subcommand(Command::new("deploy"))
+}
+
+fn names(args: &[clap_show::DocArg]) -> Vec<&str> {
+    args.iter().map(|t| t.flags.as_str()).collect()
+}
+
+#[test]
+fn extracts_sections_in_first_seen_order() {
+    let page = clap_show::extract(&command());
+    let main = &page.main;
+
+    assert_eq!(names(&main.arguments), ["[input]"]);
+    assert_eq!(names(&main.options), ["--verbose", "-h, --help"]);
+
+    let headings = main.sections.iter().map(|t| t.heading.as_str()).collect::<Vec<_>>();
+    assert_eq!(headings, ["Output", "Safety"]);
+    // Positional arguments are listed under their heading too
+    assert_eq!(
+        names(&main.sections[0].args),
+        ["--output <output>", "[template]", "--color <color>"]
+    );
+    assert_eq!(names(&main.sections[1].args), ["--dry-run"]);
+}
+
+#[test]
+fn extracts_subcommand_heading() {
+    let page = clap_show::extract(&command());
+
+    assert_eq!(page.main.commands_heading, "Tasks");
+    assert_eq!(page.subcommands[0].commands_heading, "Commands");
+}
+
+#[test]
+fn markdown_shows_sections() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+
+    let headings = markdown
+        .lines()
+        .filter(|t| t.starts_with("### "))
+        .take(5)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        headings,
+        ["### Tasks", "### Arguments", "### Options", "### Output", "### Safety"]
+    );
+    assert!(markdown.contains(
+        "### Output\n\n| Argument | Description |\n| --- | --- |\n\
+         | `--output <output>` |  |\n| `[template]` |  |\n| `--color <color>` |  |\n"
+    ));
+}
+
+#[cfg(any(feature = "handlebars", feature = "ramhorns"))]
+#[test]
+fn html_shows_sections() {
+    for engine in common::engines() {
+        let html = clap_show::ClapShow::new(&command()).engine(engine).render().unwrap();
+
+        let tasks = html.find("<h2>Tasks</h2>").unwrap();
+        let output = html.find("<h2>Output</h2>").unwrap();
+        let safety = html.find("<h2>Safety</h2>").unwrap();
+        assert!(tasks < output && output < safety, "{:?}", engine);
+        assert!(html[output..safety].contains("<dt id=\"mycli--template\">"), "{:?}", engine);
+    }
+}
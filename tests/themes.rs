@@ -0,0 +1,87 @@
+//! The themes, the replaced stylesheet and the custom CSS reach every HTML page.
+
+#![cfg(any(feature = "handlebars", feature = "ramhorns"))]
+
+use clap::Command;
+use clap_show::{ClapShow, Theme};
+use common::{engines, outputs};
+
+mod common;
+
+fn command() -> Command {
+    Command::new("mycli").subcommand(Command::new("deploy"))
+}
+
+#[test]
+fn default_theme_follows_the_system() {
+    for engine in engines() {
+        for html in outputs(ClapShow::new(&command()).engine(engine)) {
+            assert!(html.contains("color-scheme: light dark;"), "{:?}", engine);
+            assert!(html.contains("--accent-color: purple;"), "{:?}", engine);
+            assert!(
+                html.contains("@media (prefers-color-scheme: dark) {\n  :root {\n    --background: #1e1e1e;"),
+                "{:?}",
+                engine
+            );
+        }
+    }
+}
+
+#[test]
+fn light_theme() {
+    for engine in engines() {
+        for html in outputs(ClapShow::new(&command()).engine(engine).theme(Theme::Light)) {
+            assert!(html.contains("color-scheme: light;"), "{:?}", engine);
+            assert!(html.contains("--accent-color: #0969da;"), "{:?}", engine);
+            assert!(html.contains("--font-family: system-ui,"), "{:?}", engine);
+            assert!(!html.contains("prefers-color-scheme"), "{:?}", engine);
+        }
+    }
+}
+
+#[test]
+fn dark_theme() {
+    for engine in engines() {
+        for html in outputs(ClapShow::new(&command()).engine(engine).theme(Theme::Dark)) {
+            assert!(html.contains("color-scheme: dark;"), "{:?}", engine);
+            assert!(html.contains("--background: #1e1e1e;"), "{:?}", engine);
+            assert!(html.contains("--accent-color: #c792ea;"), "{:?}", engine);
+            assert!(!html.contains("prefers-color-scheme"), "{:?}", engine);
+        }
+    }
+}
+
+#[test]
+fn custom_css_comes_after_the_theme() {
+    let command = command();
+    for engine in engines() {
+        let builder = ClapShow::new(&command)
+            .engine(engine)
+            .theme(Theme::Dark)
+            .css(":root { --accent-color: orange; }")
+            .css("h1 { margin: 0; }");
+        for html in outputs(builder) {
+            let theme = html.find("--accent-color: #c792ea;").unwrap();
+            let first = html.find(":root { --accent-color: orange; }").unwrap();
+            let second = html.find("h1 { margin: 0; }").unwrap();
+            assert!(theme < first && first < second, "{:?}", engine);
+        }
+    }
+}
+
+#[test]
+fn stylesheet_replaces_the_theme() {
+    let command = command();
+    for engine in engines() {
+        let builder = ClapShow::new(&command)
+            .engine(engine)
+            .theme(Theme::Dark)
+            .stylesheet("body { color: teal; }")
+            .css("h1 { margin: 0; }");
+        for html in outputs(builder) {
+            assert!(html.contains("body { color: teal; }\nh1 { margin: 0; }"), "{:?}", engine);
+            assert!(!html.contains("--accent-color"), "{:?}", engine);
+            assert!(!html.contains("color-scheme"), "{:?}", engine);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Default values, possible values and environment variables are documented,
+//! unless clap hides them.
+
+use clap::builder::PossibleValue;
+use clap::{Arg, ArgAction, Command};
+use pretty_assertions::assert_eq;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .arg(
+            Arg::new("level")
+                .long("level")
+                .default_value("info")
+                .value_parser([
+                    PossibleValue::new("info").help("Normal output"),
+                    PossibleValue::new("debug"),
+                    PossibleValue::new("trace").hide(true),
+                ]),
+        )
+        .arg(
+            Arg::new("tags")
+                .long("tags")
+                .action(ArgAction::Append)
+                .env("MYCLI_TAGS")
+                .value_delimiter(',')
+                .default_values(["web", "db"]),
+        )
+        .arg(
+            Arg::new("secret")
+                .long("secret")
+                .env("MYCLI_SECRET")
+                .hide_env(true)
+                .default_value("hunter2")
+                .hide_default_value(true)
+                .value_parser(["plain", "hashed"])
+                .hide_possible_values(true),
+        )
+        .arg(Arg::new("fast").long("fast").action(ArgAction::SetTrue))
+}
+
+#[test]
+fn extracts_values() {
+    let page = clap_show::extract(&command());
+    let [level, tags, secret, fast, ..] = &page.main.options[..] else {
+        panic!("missing options");
+    };
+
+    assert_eq!(level.default_values, ["info"]);
+    let values = level
+        .possible_values
+        .iter()
+        .map(|t| (t.name.as_str(), t.description.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(values, [("info", "Normal output"), ("debug", "")]);
+    assert_eq!(level.env, None);
+    assert_eq!(level.value_delimiter, None);
+
+    assert_eq!(tags.default_values, ["web", "db"]);
+    assert_eq!(tags.env.as_deref(), Some("MYCLI_TAGS"));
+    assert_eq!(tags.value_delimiter, Some(','));
+
+    assert!(secret.default_values.is_empty());
+    assert!(secret.possible_values.is_empty());
+    assert_eq!(secret.env, None);
+
+    // Flags have no values, not even their implicit `false`
+    assert!(fast.default_values.is_empty());
+    assert!(fast.possible_values.is_empty());
+}
+
+#[test]
+fn markdown_shows_values() {
+    let markdown = clap_show::render_markdown(&command()).unwrap();
+
+    assert!(markdown.contains(
+        "| `--level <level>` | Default: `info`<br>Possible values:<br>\
+         - `info`: Normal output<br>- `debug` |"
+    ));
+    assert!(markdown.contains(
+        "| `--tags <tags>` | Default: `web`, `db`<br>Environment: `MYCLI_TAGS`<br>\
+         Values separated by `,` |"
+    ));
+    assert!(markdown.contains("| `--secret <secret>` |  |"));
+    assert!(!markdown.contains("trace"));
+    assert!(!markdown.contains("hunter2"));
+    assert!(!markdown.contains("MYCLI_SECRET"));
+    assert!(!markdown.contains("hashed"));
+}
+
+#[test]
+fn man_pages_show_values() {
+    let pages = clap_show::render_man_pages(&command()).unwrap();
+    let man = &pages[0].content;
+
+    assert!(man.contains(
+        "\\fB\\-\\-level\\fR <level>\n[default: info]\n.IP\nPossible values:\n.RS\n\
+         .IP \\(bu 2\n\\fBinfo\\fR: Normal output\n.IP \\(bu 2\n\\fBdebug\\fR\n.RE\n"
+    ));
+    assert!(man.contains("[default: web, db]\n.IP\n[env: MYCLI_TAGS]\n.IP\n[values separated by: ,]\n"));
+    assert!(man.contains("\\fB\\-\\-secret\\fR <secret>\n.TP\n"));
+    assert!(!man.contains("trace"));
+    assert!(!man.contains("hunter2"));
+    assert!(!man.contains("MYCLI_SECRET"));
+}
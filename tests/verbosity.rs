@@ -0,0 +1,61 @@
+//! Arguments and subcommands fall back between their short and long help,
+//! whichever `Verbosity` is picked.
+
+use clap::{Arg, Command};
+use clap_show::{ClapShow, Verbosity};
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+fn command() -> Command {
+    Command::new("mycli")
+        .disable_help_flag(true)
+        .disable_help_subcommand(true)
+        .arg(Arg::new("short").long("short").help("Short help"))
+        .arg(Arg::new("long").long("long").long_help("Long help"))
+        .arg(Arg::new("both").long("both").help("Short help").long_help("Long help"))
+        .subcommand(Command::new("deploy").long_about("Deploy the application"))
+}
+
+/// Descriptions of the options and subcommands of the main command, by name.
+fn descriptions(verbosity: Verbosity) -> Vec<(String, String)> {
+    let json = ClapShow::new(&command()).verbosity(verbosity).render_json().unwrap();
+    let json: Value = serde_json::from_str(&json).unwrap();
+    let main = &json["main"];
+
+    let options = main["options"].as_array().unwrap().iter().map(|t| (&t["flags"], &t["description"]));
+    let commands = main["commands"].as_array().unwrap().iter().map(|t| (&t["name"], &t["description"]));
+    options
+        .chain(commands)
+        .map(|(name, description)| (name.as_str().unwrap().to_string(), description.as_str().unwrap().to_string()))
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+}
+
+#[test]
+fn summary_prefers_short_help() {
+    assert_eq!(
+        descriptions(Verbosity::Summary),
+        pairs(&[
+            ("--short <short>", "Short help"),
+            ("--long <long>", "Long help"),
+            ("--both <both>", "Short help"),
+            ("deploy", "Deploy the application"),
+        ])
+    );
+}
+
+#[test]
+fn full_prefers_long_help() {
+    assert_eq!(
+        descriptions(Verbosity::Full),
+        pairs(&[
+            ("--short <short>", "Short help"),
+            ("--long <long>", "Long help"),
+            ("--both <both>", "Long help"),
+            ("deploy", "Deploy the application"),
+        ])
+    );
+}